@@ -1,4 +1,7 @@
-use aviation_wx::{decode_message, DecodeOptions, DetailLevel, MessageType};
+use aviation_wx::{
+    decode_message, decode_notam_bulletin, extract_geometry, geometry_to_geojson, normalize_notam, notam_to_aixm,
+    parse_notam, DecodeOptions, DetailLevel, MessageType,
+};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -14,6 +17,7 @@ pub fn decode_json(raw: &str, type_hint: &str) -> Result<String, JsValue> {
         type_hint,
         lang: "en".to_string(),
         detail: DetailLevel::Normal,
+        dialect: None,
         output_json: true,
         output_explain: false,
     };
@@ -22,3 +26,33 @@ pub fn decode_json(raw: &str, type_hint: &str) -> Result<String, JsValue> {
     let json = serde_json::to_string(&response).map_err(|e| JsValue::from_str(&e.to_string()))?;
     Ok(json)
 }
+
+/// Each NOTAM of a bulletin or PIB with its byte range, as a JSON array.
+#[wasm_bindgen]
+pub fn decode_notam_bulletin_json(raw: &str) -> Result<String, JsValue> {
+    let options = DecodeOptions {
+        type_hint: MessageType::Notam,
+        lang: "en".to_string(),
+        detail: DetailLevel::Normal,
+        dialect: None,
+        output_json: true,
+        output_explain: false,
+    };
+
+    let entries = decode_notam_bulletin(raw, &options);
+    serde_json::to_string(&entries).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// GeoJSON `FeatureCollection` of the Q) line area and E) shapes of a NOTAM.
+#[wasm_bindgen]
+pub fn notam_geojson(raw: &str) -> Result<String, JsValue> {
+    let normalized = normalize_notam(&parse_notam(raw).0);
+    let geojson = geometry_to_geojson(&extract_geometry(&normalized));
+    serde_json::to_string(&geojson).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// AIXM 5.1 Digital NOTAM (Event schema) XML of a NOTAM.
+#[wasm_bindgen]
+pub fn notam_aixm(raw: &str) -> String {
+    notam_to_aixm(&normalize_notam(&parse_notam(raw).0))
+}
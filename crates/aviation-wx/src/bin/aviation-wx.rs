@@ -1,6 +1,6 @@
 use std::{fs, path::PathBuf};
 
-use aviation_wx::{decode_message, DecodeOptions, DetailLevel, MessageType, NormalizedMessage, ParsedMessage};
+use aviation_wx::{decode_message, decode_notam_bulletin, DecodeOptions, DetailLevel, Dialect, MessageType, NormalizedMessage, ParsedMessage};
 use clap::{Parser, ValueEnum};
 use serde::Serialize;
 
@@ -23,6 +23,29 @@ impl From<MessageTypeArg> for MessageType {
     }
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DialectArg {
+    Auto,
+    Icao,
+    Faa,
+    Caac,
+    Canada,
+    Australia,
+}
+
+impl From<DialectArg> for Option<Dialect> {
+    fn from(value: DialectArg) -> Self {
+        match value {
+            DialectArg::Auto => None,
+            DialectArg::Icao => Some(Dialect::Icao),
+            DialectArg::Faa => Some(Dialect::Faa),
+            DialectArg::Caac => Some(Dialect::Caac),
+            DialectArg::Canada => Some(Dialect::Canada),
+            DialectArg::Australia => Some(Dialect::Australia),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum OutputMode {
     WithRaw,
@@ -41,8 +64,13 @@ struct Cli {
     batch: Option<PathBuf>,
     #[arg(long, value_enum, default_value = "auto")]
     r#type: MessageTypeArg,
+    #[arg(long, value_enum, default_value = "auto")]
+    dialect: DialectArg,
     #[arg(long, value_enum, default_value = "with-raw")]
     mode: OutputMode,
+    /// Split the input into its NOTAMs and decode each one.
+    #[arg(long, conflicts_with = "batch")]
+    bulletin: bool,
     #[arg(long, value_name = "FILE")]
     out: Option<PathBuf>,
     #[arg(long)]
@@ -65,6 +93,14 @@ struct CliOutput {
     errors: Vec<aviation_wx::Issue>,
 }
 
+#[derive(Debug, Serialize)]
+struct CliBulletinEntry {
+    start: usize,
+    end: usize,
+    #[serde(flatten)]
+    output: CliOutput,
+}
+
 impl CliOutput {
     fn from_response(response: aviation_wx::DecodeResponse, mode: OutputMode) -> Self {
         let raw = match mode {
@@ -99,10 +135,24 @@ fn main() -> anyhow::Result<()> {
         type_hint: cli.r#type.into(),
         lang: "en".to_string(),
         detail: DetailLevel::Normal,
+        dialect: cli.dialect.into(),
         output_json: true,
         output_explain: false,
     };
 
+    if cli.bulletin {
+        let entries: Vec<CliBulletinEntry> = decode_notam_bulletin(&messages[0], &options)
+            .into_iter()
+            .map(|entry| CliBulletinEntry {
+                start: entry.start,
+                end: entry.end,
+                output: CliOutput::from_response(entry.response, cli.mode),
+            })
+            .collect();
+        let has_errors = entries.is_empty() || entries.iter().any(|item| !item.output.errors.is_empty());
+        return emit(&cli, serde_json::to_value(entries)?, has_errors);
+    }
+
     let outputs: Vec<CliOutput> = messages
         .iter()
         .map(|message| CliOutput::from_response(decode_message(message, &options), cli.mode))
@@ -116,13 +166,17 @@ fn main() -> anyhow::Result<()> {
         serde_json::to_value(outputs.into_iter().next().unwrap())?
     };
 
+    emit(&cli, payload, has_errors)
+}
+
+fn emit(cli: &Cli, payload: serde_json::Value, has_errors: bool) -> anyhow::Result<()> {
     let rendered = if cli.pretty {
         serde_json::to_string_pretty(&payload)?
     } else {
         serde_json::to_string(&payload)?
     };
 
-    if let Some(path) = cli.out {
+    if let Some(path) = cli.out.as_ref() {
         fs::write(path, rendered)?;
     } else {
         println!("{}", rendered);
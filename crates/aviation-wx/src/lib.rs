@@ -1,15 +1,33 @@
 ﻿use aviation_wx_core as core;
+use serde::{Deserialize, Serialize};
 
 pub use core::*;
-pub use aviation_wx_metar::{decode_metar, normalize_metar, parse_metar, translate_metar};
-pub use aviation_wx_notam::{decode_notam, normalize_notam, parse_notam, translate_notam};
-pub use aviation_wx_taf::{decode_taf, normalize_taf, parse_taf, translate_taf};
+pub use aviation_wx_metar::{
+    decode_metar, decode_metar_with_dialect, describe_metar_diff, diff_metar, diff_metar_with_rules, normalize_metar,
+    parse_metar, parse_metar_with_dialect, translate_metar, MetarChange, MetarChangeKind, MetarDiff, SpeciRules,
+};
+pub use aviation_wx_notam::{
+    active_intervals, body_term, caac_body_terms, compose_notam, decode_notam, decode_notam_with_dialect, expand_body,
+    expand_schedule, extract_geometry, extract_records, geometry_to_geojson, header_issues, is_active_at, normalize_notam,
+    notam_to_aixm, parse_faa_notam, parse_header, parse_notam, parse_notam_datetime, parse_notam_id,
+    parse_notam_with_dialect, parse_period, parse_q_line, parse_schedule, parse_vertical_limit, period_issues,
+    q_line_issues, split_notams, text_geometry, translate_notam, vertical_issues, GeoPoint, GeometryShape, GeometrySource,
+    NotamComposition, NotamDraft, NotamGeometry, NotamSegment, NotamStore,
+};
+pub use aviation_wx_taf::{
+    conditions_at, decode_taf, decode_taf_with_dialect, describe_taf_diff, diff_taf, expand_timeline, hourly_timeline,
+    normalize_taf, parse_taf, parse_taf_with_dialect, translate_taf, validate_taf, verify_taf, TafAlternative,
+    TafConditions, TafDiff, TafElement, TafPeriodChange, TafTimelinePeriod, TafValidation, TafVerification,
+    TafVerificationEntry, TafVerificationSummary,
+};
 
 #[derive(Debug, Clone)]
 pub struct DecodeOptions {
     pub type_hint: core::MessageType,
     pub lang: String,
     pub detail: core::DetailLevel,
+    /// Overrides the dialect inferred from the station; `None` infers it.
+    pub dialect: Option<core::Dialect>,
     pub output_json: bool,
     pub output_explain: bool,
 }
@@ -20,6 +38,7 @@ impl Default for DecodeOptions {
             type_hint: core::MessageType::Unknown,
             lang: "zh-CN".to_string(),
             detail: core::DetailLevel::Normal,
+            dialect: None,
             output_json: true,
             output_explain: true,
         }
@@ -37,9 +56,21 @@ pub fn decode_message(raw: &str, options: &DecodeOptions) -> core::DecodeRespons
     };
 
     let mut response = match target {
-        core::MessageType::Taf => aviation_wx_taf::decode_taf(sanitized_raw, options.detail, &options.lang),
-        core::MessageType::Metar => aviation_wx_metar::decode_metar(sanitized_raw, options.detail, &options.lang),
-        core::MessageType::Notam => aviation_wx_notam::decode_notam(sanitized_raw, options.detail, &options.lang),
+        core::MessageType::Taf => {
+            aviation_wx_taf::decode_taf_with_dialect(sanitized_raw, options.dialect, options.detail, &options.lang)
+        }
+        core::MessageType::Metar => {
+            aviation_wx_metar::decode_metar_with_dialect(sanitized_raw, options.dialect, options.detail, &options.lang)
+        }
+        core::MessageType::Notam => match notams_with_headers(sanitized_raw).as_slice() {
+            segments @ [_, _, ..] => multiple_notams_response(sanitized_raw, segments),
+            _ => aviation_wx_notam::decode_notam_with_dialect(
+                sanitized_raw,
+                options.dialect,
+                options.detail,
+                &options.lang,
+            ),
+        },
         core::MessageType::Unknown => core::DecodeResponse {
             schema_version: "1.0".to_string(),
             message_type: core::MessageType::Unknown,
@@ -102,3 +133,71 @@ pub fn decode_message(raw: &str, options: &DecodeOptions) -> core::DecodeRespons
     response
 }
 
+/// Segments of `raw` that start with an ICAO or FAA NOTAM header.
+fn notams_with_headers(raw: &str) -> Vec<NotamSegment> {
+    split_notams(raw)
+        .into_iter()
+        .filter(|segment| {
+            let (parsed, _) = parse_notam(&raw[segment.start..segment.end]);
+            parsed.header.is_some() || parsed.faa_header.is_some()
+        })
+        .collect()
+}
+
+/// Rejects input holding several NOTAMs, which `decode_message` would otherwise read as
+/// one NOTAM with the fields of the last; `decode_notam_bulletin` decodes each of them.
+fn multiple_notams_response(raw: &str, segments: &[NotamSegment]) -> core::DecodeResponse {
+    let ranges: Vec<String> = segments
+        .iter()
+        .map(|segment| format!("{}-{}", segment.start, segment.end))
+        .collect();
+    core::DecodeResponse {
+        schema_version: "1.0".to_string(),
+        message_type: core::MessageType::Notam,
+        requested_type: core::MessageType::Unknown,
+        detected_type: core::MessageType::Unknown,
+        final_type: core::MessageType::Notam,
+        raw: raw.trim().to_string(),
+        parsed: None,
+        normalized: None,
+        explain: None,
+        warnings: Vec::new(),
+        errors: vec![core::issue(
+            "notam_multiple",
+            format!(
+                "Input contains {} NOTAMs (bytes {}); decode it as a NOTAM bulletin.",
+                segments.len(),
+                ranges.join(", ")
+            ),
+            None,
+        )],
+        warnings_legacy: None,
+        errors_legacy: None,
+    }
+}
+
+/// One NOTAM of a bulletin with its byte range `[start, end)` in the input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotamBulletinEntry {
+    pub start: usize,
+    pub end: usize,
+    pub response: core::DecodeResponse,
+}
+
+/// Splits a NOTAM bulletin or PIB into its NOTAMs (`split_notams`) and decodes each one
+/// as a NOTAM with `options`.
+pub fn decode_notam_bulletin(raw: &str, options: &DecodeOptions) -> Vec<NotamBulletinEntry> {
+    let options = DecodeOptions {
+        type_hint: core::MessageType::Notam,
+        ..options.clone()
+    };
+    split_notams(raw)
+        .into_iter()
+        .map(|segment| NotamBulletinEntry {
+            start: segment.start,
+            end: segment.end,
+            response: decode_message(&raw[segment.start..segment.end], &options),
+        })
+        .collect()
+}
+
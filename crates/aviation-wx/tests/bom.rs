@@ -5,6 +5,7 @@ fn default_options() -> DecodeOptions {
         type_hint: MessageType::Unknown,
         lang: "zh-CN".to_string(),
         detail: DetailLevel::Normal,
+        dialect: None,
         output_json: true,
         output_explain: true,
     }
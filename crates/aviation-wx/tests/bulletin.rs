@@ -0,0 +1,45 @@
+use aviation_wx::{decode_message, decode_notam_bulletin, DecodeOptions, MessageType, NormalizedMessage};
+
+#[test]
+fn decodes_each_notam_of_a_pib_with_offsets() {
+    let input = "\u{FEFF}ZSPD SHANGHAI/PUDONG\n\
+                 (A1234/24 NOTAMN Q) ZSHA/QMRLC/IV/NBO/A/000/999/3112N12130E005\n\
+                 A) ZSPD B) 2402010000 C) 2402012359 E) RWY 17L/35R CLSD)\n\
+                 (A1235/24 NOTAMN Q) ZSHA/QMXLC/IV/BO/A/000/999/3112N12130E005\n\
+                 A) ZSPD B) 2402010000 C) 2402020000 E) TWY B CLSD)\n";
+    let entries = decode_notam_bulletin(input, &DecodeOptions::default());
+    assert_eq!(entries.len(), 2);
+    for entry in &entries {
+        assert_eq!(entry.response.final_type, MessageType::Notam);
+        assert!(input[entry.start..entry.end].starts_with("(A123"));
+        assert!(entry.response.errors.is_empty(), "{:?}", entry.response.errors);
+    }
+    let Some(NormalizedMessage::Notam(second)) = &entries[1].response.normalized else {
+        panic!("expected a normalized NOTAM");
+    };
+    assert_eq!(second.e.as_deref(), Some("TWY B CLSD"));
+}
+
+#[test]
+fn decode_message_rejects_several_notams() {
+    let input = "(A1234/24 NOTAMN A) ZSPD B) 2402010000 C) 2402012359 E) RWY 17L/35R CLSD)\n\
+                 (A1235/24 NOTAMN A) ZSPD B) 2402010000 C) 2402020000 E) TWY B CLSD)";
+    let response = decode_message(input, &DecodeOptions::default());
+    assert_eq!(response.final_type, MessageType::Notam);
+    assert!(response.parsed.is_none());
+    assert_eq!(response.errors[0].code, "notam_multiple");
+    assert!(response.errors[0].message.contains("bytes 0-73, 74-141"), "{}", response.errors[0].message);
+
+    let single = decode_message(&input[..73], &DecodeOptions::default());
+    assert!(single.errors.is_empty(), "{:?}", single.errors);
+
+    let gapped = decode_message(
+        "B0002/24 NOTAMN A) EGLL B) 2402010000 C) PERM\n\nE) CRANE ERECTED",
+        &DecodeOptions::default(),
+    );
+    assert!(gapped.errors.is_empty(), "{:?}", gapped.errors);
+    let Some(NormalizedMessage::Notam(notam)) = &gapped.normalized else {
+        panic!("expected a NOTAM: {:?}", gapped.normalized);
+    };
+    assert_eq!(notam.e.as_deref(), Some("CRANE ERECTED"));
+}
@@ -44,6 +44,7 @@ fn decode_all(subdir: &str, type_hint: MessageType) -> Vec<GoldenRecord> {
         type_hint,
         lang: "zh-CN".to_string(),
         detail: DetailLevel::Normal,
+        dialect: None,
         output_json: true,
         output_explain: true,
     };
@@ -12,7 +12,7 @@ use tower_http::{
     trace::TraceLayer,
 };
 
-use aviation_wx::{decode_message, DecodeOptions, DetailLevel, MessageType};
+use aviation_wx::{decode_message, decode_notam_bulletin, DecodeOptions, DetailLevel, Dialect, MessageType};
 use aviation_wx_core as core;
 
 const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
@@ -33,6 +33,8 @@ struct DecodeRequest {
     lang: String,
     #[serde(default = "default_detail")]
     detail: String,
+    #[serde(default)]
+    dialect: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -52,6 +54,8 @@ struct BatchDecodeRequest {
     lang: String,
     #[serde(default = "default_detail")]
     detail: String,
+    #[serde(default)]
+    dialect: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -60,6 +64,11 @@ struct BatchDecodeResponse {
     errors: Vec<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct NotamBulletinResponse {
+    notams: Vec<aviation_wx::NotamBulletinEntry>,
+}
+
 fn default_type() -> String {
     "auto".to_string()
 }
@@ -80,6 +89,10 @@ fn parse_detail(value: &str) -> DetailLevel {
     }
 }
 
+fn parse_dialect(value: Option<&str>) -> Option<Dialect> {
+    value.and_then(|value| value.parse::<Dialect>().ok())
+}
+
 fn parse_message_type(value: &str) -> MessageType {
     match value.to_ascii_lowercase().as_str() {
         "auto" => MessageType::Unknown,
@@ -91,7 +104,7 @@ fn parse_message_type(value: &str) -> MessageType {
 }
 
 pub fn build_app() -> Router {
-    let request_id = MakeRequestUuid::default();
+    let request_id = MakeRequestUuid;
     let trace_layer = TraceLayer::new_for_http().make_span_with(|request: &Request<_>| {
         let request_id = request
             .headers()
@@ -109,6 +122,7 @@ pub fn build_app() -> Router {
         .route("/healthz", get(health))
         .route("/v1/decode", post(decode))
         .route("/v1/decode/batch", post(decode_batch))
+        .route("/v1/decode/notam-bulletin", post(decode_bulletin))
         .layer(
             ServiceBuilder::new()
                 .layer(auth_layer())
@@ -157,6 +171,7 @@ async fn decode(Json(payload): Json<DecodeRequest>) -> impl IntoResponse {
         type_hint: parse_message_type(&payload.r#type),
         lang: payload.lang,
         detail: parse_detail(&payload.detail),
+        dialect: parse_dialect(payload.dialect.as_deref()),
         output_json: payload.output.json.unwrap_or(true),
         output_explain: payload.output.explain.unwrap_or(true),
     };
@@ -180,6 +195,7 @@ async fn decode_batch(Json(payload): Json<BatchDecodeRequest>) -> impl IntoRespo
         type_hint: parse_message_type(&payload.r#type),
         lang: payload.lang,
         detail: parse_detail(&payload.detail),
+        dialect: parse_dialect(payload.dialect.as_deref()),
         output_json: payload.output.json.unwrap_or(true),
         output_explain: payload.output.explain.unwrap_or(true),
     };
@@ -218,3 +234,17 @@ async fn decode_batch(Json(payload): Json<BatchDecodeRequest>) -> impl IntoRespo
     (StatusCode::OK, Json(BatchDecodeResponse { results, errors }))
 }
 
+async fn decode_bulletin(Json(payload): Json<DecodeRequest>) -> impl IntoResponse {
+    let options = DecodeOptions {
+        type_hint: MessageType::Notam,
+        lang: payload.lang,
+        detail: parse_detail(&payload.detail),
+        dialect: parse_dialect(payload.dialect.as_deref()),
+        output_json: payload.output.json.unwrap_or(true),
+        output_explain: payload.output.explain.unwrap_or(true),
+    };
+
+    let notams = decode_notam_bulletin(&payload.message, &options);
+    tracing::info!(notams = notams.len(), "decode completed (notam bulletin)");
+    (StatusCode::OK, Json(NotamBulletinResponse { notams }))
+}
@@ -35,3 +35,31 @@ async fn decode_ok() {
         .unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn decode_notam_bulletin_ok() {
+    let app = backend::build_app();
+    let payload = serde_json::json!({
+        "message": "(A1234/24 NOTAMN A) ZSPD B) 2402010000 C) 2402012359 E) RWY 17L/35R CLSD)\n\
+                    (A1235/24 NOTAMN A) ZSPD B) 2402010000 C) 2402020000 E) TWY B CLSD)",
+        "lang": "en"
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/decode/notam-bulletin")
+                .header("content-type", "application/json")
+                .body(Body::from(payload.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let notams = json["notams"].as_array().unwrap();
+    assert_eq!(notams.len(), 2);
+    assert_eq!(notams[1]["start"], 74);
+    assert_eq!(notams[1]["response"]["final_type"], "notam");
+}
@@ -42,17 +42,109 @@ impl std::str::FromStr for MessageType {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DetailLevel {
     Brief,
+    #[default]
     Normal,
     Full,
 }
 
-impl Default for DetailLevel {
-    fn default() -> Self {
-        DetailLevel::Normal
+/// Regional reporting practice applied when parsing METAR/TAF groups.
+///
+/// `Icao` follows WMO/ICAO Annex 3 conventions and is the fallback for
+/// stations without a known national profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    #[default]
+    Icao,
+    Faa,
+    Caac,
+    Canada,
+    Australia,
+}
+
+impl Dialect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Dialect::Icao => "icao",
+            Dialect::Faa => "faa",
+            Dialect::Caac => "caac",
+            Dialect::Canada => "canada",
+            Dialect::Australia => "australia",
+        }
+    }
+
+    /// Infers the dialect from the ICAO location indicator of the station.
+    pub fn from_station(station: &str) -> Dialect {
+        let upper = station.to_ascii_uppercase();
+        if upper.len() != 4 {
+            return Dialect::Icao;
+        }
+        let prefix = &upper[..2];
+        match upper.as_bytes()[0] {
+            b'K' => Dialect::Faa,
+            b'P' if matches!(prefix, "PA" | "PF" | "PG" | "PH" | "PO" | "PP") => Dialect::Faa,
+            b'T' if prefix == "TJ" => Dialect::Faa,
+            b'Z' if !matches!(prefix, "ZK" | "ZM") => Dialect::Caac,
+            b'C' => Dialect::Canada,
+            b'Y' => Dialect::Australia,
+            _ => Dialect::Icao,
+        }
+    }
+
+    /// Whether visibility is reported in statute miles (`P6SM`, `1 1/2SM`).
+    pub fn uses_statute_miles(&self) -> bool {
+        matches!(self, Dialect::Faa | Dialect::Canada)
+    }
+
+    /// Whether `CLR` (no cloud detected below 12000 ft by an automated station) is valid.
+    pub fn accepts_clr(&self) -> bool {
+        matches!(self, Dialect::Faa | Dialect::Canada)
+    }
+
+    /// Whether METAR remarks follow the FAA grammar (`AO2`, `SLPppp`, `Tsnnnsnnn`).
+    pub fn uses_faa_remarks(&self) -> bool {
+        matches!(self, Dialect::Faa)
+    }
+
+    /// Whether METAR remarks follow the Canadian grammar (`SLPppp`, cloud opacity such as `SC6AC2`).
+    pub fn uses_canadian_remarks(&self) -> bool {
+        matches!(self, Dialect::Canada)
+    }
+
+    /// Whether METAR remarks carry the Australian rainfall group (`RF00.4/012.6`).
+    pub fn uses_australian_remarks(&self) -> bool {
+        matches!(self, Dialect::Australia)
+    }
+
+    /// Whether winds reported in metres per second are explained in that unit, as CAAC
+    /// stations report them, rather than only in knots.
+    pub fn explains_wind_in_mps(&self) -> bool {
+        matches!(self, Dialect::Caac)
+    }
+}
+
+impl std::fmt::Display for Dialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Dialect {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "icao" | "wmo" => Ok(Dialect::Icao),
+            "faa" | "us" => Ok(Dialect::Faa),
+            "caac" | "cn" => Ok(Dialect::Caac),
+            "canada" | "ca" => Ok(Dialect::Canada),
+            "australia" | "au" => Ok(Dialect::Australia),
+            _ => Err(()),
+        }
     }
 }
 
@@ -69,6 +161,71 @@ pub struct ValidityPeriod {
     pub to: UtcTime,
 }
 
+impl ValidityPeriod {
+    /// Length of the month the period starts in. Messages carry no month, so when the
+    /// day rolls over this picks the longest month keeping the period within 30 hours.
+    pub fn month_days(&self) -> u8 {
+        if self.to.day >= self.from.day {
+            return 31;
+        }
+        [31u8, 30, 29, 28]
+            .into_iter()
+            .find(|days| {
+                let span = day_offset(self.from.day, self.to.day, *days) * 1440 + minute_of_day(&self.to)
+                    - minute_of_day(&self.from);
+                span <= 30 * 60
+            })
+            .unwrap_or(31)
+    }
+
+    /// Minutes from the start of the period to `time`; negative when `time` is earlier.
+    pub fn offset_minutes(&self, time: &UtcTime) -> i64 {
+        day_offset(self.from.day, time.day, self.month_days()) * 1440 + minute_of_day(time)
+            - minute_of_day(&self.from)
+    }
+
+    pub fn duration_minutes(&self) -> i64 {
+        self.offset_minutes(&self.to)
+    }
+
+    /// Whether `time` lies within the period, both ends inclusive.
+    pub fn contains(&self, time: &UtcTime) -> bool {
+        let offset = self.offset_minutes(time);
+        offset >= 0 && offset <= self.duration_minutes()
+    }
+
+    /// Time `minutes` after the start of the period, rolling the day over at month end.
+    pub fn time_at_offset(&self, minutes: i64) -> UtcTime {
+        let total = minute_of_day(&self.from) + minutes;
+        let days = total.div_euclid(1440);
+        let rest = total.rem_euclid(1440);
+        let month_days = self.month_days() as i64;
+        let day = (self.from.day as i64 - 1 + days).rem_euclid(month_days) + 1;
+        UtcTime {
+            day: day as u8,
+            hour: (rest / 60) as u8,
+            minute: (rest % 60) as u8,
+        }
+    }
+
+    /// Whether `other` lies entirely within the period.
+    pub fn covers(&self, other: &ValidityPeriod) -> bool {
+        self.contains(&other.from) && self.contains(&other.to)
+    }
+}
+
+fn minute_of_day(time: &UtcTime) -> i64 {
+    time.hour as i64 * 60 + time.minute as i64
+}
+
+fn day_offset(base_day: u8, day: u8, month_days: u8) -> i64 {
+    if day < base_day && base_day - day > 15 {
+        (day as i64 + month_days as i64) - base_day as i64
+    } else {
+        day as i64 - base_day as i64
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Wind {
     pub direction_deg: Option<u16>,
@@ -97,6 +254,10 @@ pub struct Visibility {
     pub distance: u32,
     pub unit: String,
     pub raw: String,
+    /// Statute-mile visibility in metres, kept exact for fractions such as `1 1/2SM`
+    /// that `distance` rounds to whole miles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_m: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -119,6 +280,30 @@ pub struct Pressure {
     pub unit: String,
 }
 
+/// Icing (`6IchhhT`) or turbulence (`5BhhhT`) layer forecast. `code` is the WMO
+/// intensity/type digit; the layer spans `base_ft` to `base_ft + thickness_ft`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerForecast {
+    pub code: u8,
+    pub base_ft: u32,
+    pub thickness_ft: u32,
+    pub raw: String,
+}
+
+/// Low-level wind shear group `WShhh/dddffKT`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindShear {
+    pub height_ft: u32,
+    pub wind: Wind,
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindShearNormalized {
+    pub height_ft: u32,
+    pub wind: WindNormalized,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IssueSpan {
     pub start: usize,
@@ -138,31 +323,96 @@ pub struct Issue {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TafParsed {
     pub station: Option<String>,
+    pub dialect: Dialect,
     pub issue_time: Option<UtcTime>,
     pub validity: Option<ValidityPeriod>,
     pub wind: Option<Wind>,
     pub visibility: Option<Visibility>,
     pub weather: Vec<String>,
     pub clouds: Vec<CloudLayer>,
-    pub temperatures: Vec<String>,
+    pub icing: Vec<LayerForecast>,
+    pub turbulence: Vec<LayerForecast>,
+    pub wind_shear: Option<WindShear>,
+    pub qnh: Option<Pressure>,
+    pub temperatures: Vec<TafTemperatureForecast>,
     pub trends: Vec<TafTrend>,
+    pub rmk_raw: Option<String>,
     pub raw_tokens: Vec<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TafTemperatureKind {
+    Max,
+    Min,
+}
+
+/// Forecast extreme temperature from a `TXnn/ddhhZ` or `TNMnn/ddhhZ` group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TafTemperatureForecast {
+    pub kind: TafTemperatureKind,
+    pub value_c: i16,
+    pub at: UtcTime,
+    pub raw: String,
+}
+
+/// TAF change group indicator. `Fm` replaces the forecast from `from_time`;
+/// `Prob`/`ProbTempo` carry a probability in `TafTrend::probability`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TafTrendKind {
+    Becmg,
+    Tempo,
+    Fm,
+    Prob,
+    ProbTempo,
+    Inter,
+}
+
+impl TafTrendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TafTrendKind::Becmg => "BECMG",
+            TafTrendKind::Tempo => "TEMPO",
+            TafTrendKind::Fm => "FM",
+            TafTrendKind::Prob => "PROB",
+            TafTrendKind::ProbTempo => "PROB_TEMPO",
+            TafTrendKind::Inter => "INTER",
+        }
+    }
+}
+
+impl std::fmt::Display for TafTrendKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TafTrend {
-    pub kind: String,
+    pub kind: TafTrendKind,
+    pub probability: Option<u8>,
+    pub from_time: Option<UtcTime>,
     pub period: Option<ValidityPeriod>,
     pub wind: Option<Wind>,
     pub visibility: Option<Visibility>,
     pub weather: Vec<String>,
     pub clouds: Vec<CloudLayer>,
+    /// Set by `NSW` or `CAVOK`: the group ends previously forecast weather.
+    pub clears_weather: bool,
+    /// Set by `CAVOK`, `NSC` or `SKC`: no significant cloud during the group.
+    pub clears_clouds: bool,
+    pub icing: Vec<LayerForecast>,
+    pub turbulence: Vec<LayerForecast>,
+    pub wind_shear: Option<WindShear>,
+    pub qnh: Option<Pressure>,
     pub raw_tokens: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetarParsed {
     pub station: Option<String>,
+    pub dialect: Dialect,
     pub issue_time: Option<UtcTime>,
     pub wind: Option<Wind>,
     pub wind_variation: Option<WindVariation>,
@@ -176,13 +426,334 @@ pub struct MetarParsed {
     pub pressure: Option<Pressure>,
     pub rmk_raw: Option<String>,
     pub rmk_tokens: Vec<String>,
+    pub remarks: Option<MetarRemarks>,
     pub trend: Option<String>,
     pub raw_tokens: Vec<String>,
 }
 
+/// Remarks decoded with the FAA, Canadian or Australian grammar; other dialects keep
+/// `rmk_raw` only.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetarRemarks {
+    pub station_type: Option<String>,
+    pub sea_level_pressure_hpa: Option<f32>,
+    pub temperature_precise: Option<PreciseTemperature>,
+    /// Canadian cloud opacity in oktas per layer, from `SC6AC2`.
+    pub cloud_opacity: Vec<CloudOpacity>,
+    /// Australian rainfall, from `RF00.4/012.6`.
+    pub rainfall: Option<Rainfall>,
+    pub unparsed: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloudOpacity {
+    pub cloud_type: String,
+    pub oktas: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rainfall {
+    pub last_10_min_mm: f32,
+    pub since_9am_mm: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreciseTemperature {
+    pub temperature_c: f32,
+    pub dewpoint_c: Option<f32>,
+}
+
+/// Traffic qualifier of the NOTAM Q) line (`I`, `V`, `IV`, `K`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QTraffic {
+    Ifr,
+    Vfr,
+    Checklist,
+}
+
+/// Purpose qualifier of the NOTAM Q) line (`N`, `B`, `O`, `M`, `K`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QPurpose {
+    ImmediateAttention,
+    Briefing,
+    OperationallySignificant,
+    Miscellaneous,
+    Checklist,
+}
+
+/// Scope qualifier of the NOTAM Q) line (`A`, `E`, `W`, `K`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QScope {
+    Aerodrome,
+    EnRoute,
+    NavigationWarning,
+    Checklist,
+}
+
+/// Structured NOTAM Q) line, e.g. `ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005`.
+/// Subject and condition texts are the English Q-code meanings when known.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QLine {
+    pub fir: String,
+    pub code: String,
+    pub subject: String,
+    pub subject_text: Option<String>,
+    pub condition: String,
+    pub condition_text: Option<String>,
+    pub traffic: Vec<QTraffic>,
+    pub purpose: Vec<QPurpose>,
+    pub scope: Vec<QScope>,
+    pub lower_fl: Option<u16>,
+    pub upper_fl: Option<u16>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub radius_nm: Option<u16>,
+    pub raw: String,
+}
+
+/// NOTAM series, number and year, e.g. `A1234/24`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NotamId {
+    pub series: String,
+    pub number: u16,
+    /// Two-digit year as written in the identifier.
+    pub year: u8,
+}
+
+impl std::fmt::Display for NotamId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{:04}/{:02}", self.series, self.number, self.year)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotamKind {
+    New,
+    Replace,
+    Cancel,
+}
+
+/// NOTAM header line such as `A1234/24 NOTAMR A1200/24`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotamHeader {
+    pub id: NotamId,
+    pub kind: NotamKind,
+    /// NOTAM replaced (`NOTAMR`) or cancelled (`NOTAMC`).
+    pub references: Option<NotamId>,
+    pub raw: String,
+}
+
+/// Full UTC date and time as used by NOTAM B) and C) fields (`YYMMDDhhmm`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct NotamDateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl NotamDateTime {
+    /// Builds a date-time, returning `None` when any component is out of range.
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) || hour > 23 || minute > 59 {
+            return None;
+        }
+        Some(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+        })
+    }
+
+    /// Minutes since 1970-01-01 00:00Z.
+    pub fn to_minutes(&self) -> i64 {
+        days_from_civil(self.year as i64, self.month as i64, self.day as i64) * 1440
+            + self.hour as i64 * 60
+            + self.minute as i64
+    }
+
+    /// Inverse of [`NotamDateTime::to_minutes`].
+    pub fn from_minutes(minutes: i64) -> Self {
+        let days = minutes.div_euclid(1440);
+        let rest = minutes.rem_euclid(1440);
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (rest / 60) as u8,
+            minute: (rest % 60) as u8,
+        }
+    }
+
+    /// Day of week, 0 = Monday through 6 = Sunday.
+    pub fn weekday(&self) -> u8 {
+        (days_from_civil(self.year as i64, self.month as i64, self.day as i64) + 3).rem_euclid(7) as u8
+    }
+}
+
+impl std::fmt::Display for NotamDateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+pub fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Effective period from the NOTAM B) and C) fields. `end` is `None` for `PERM`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotamPeriod {
+    pub start: NotamDateTime,
+    pub end: Option<NotamDateTime>,
+    /// C) carried `EST`: the end time is an estimate and needs a replacing NOTAM.
+    pub estimated: bool,
+    pub permanent: bool,
+}
+
+impl NotamPeriod {
+    /// Whether `time` falls within `[start, end)`; a permanent NOTAM never ends.
+    pub fn contains(&self, time: &NotamDateTime) -> bool {
+        *time >= self.start && self.end.is_none_or(|end| *time < end)
+    }
+}
+
+/// Start or end of a NOTAM D) time range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleTime {
+    /// Minutes after 00:00Z; `2400` is 1440.
+    Utc(u16),
+    Sunrise,
+    Sunset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleTimeRange {
+    pub start: ScheduleTime,
+    pub end: ScheduleTime,
+}
+
+/// One D) item: the days it applies to and its time ranges. Empty `weekdays`
+/// and `dates` mean every day; a range ending at or before its start ends the next day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleRule {
+    /// 0 = Monday through 6 = Sunday.
+    pub weekdays: Vec<u8>,
+    pub month: Option<u8>,
+    pub dates: Vec<u8>,
+    pub times: Vec<ScheduleTimeRange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotamSchedule {
+    pub rules: Vec<ScheduleRule>,
+    pub raw: String,
+}
+
+/// A concrete active interval `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotamInterval {
+    pub start: NotamDateTime,
+    pub end: NotamDateTime,
+}
+
+/// Identification of an FAA domestic NOTAM, `!JFK 01/123 JFK RWY ...`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaaNotamHeader {
+    /// Accountability code after `!`, e.g. `JFK` or `FDC`.
+    pub accountability: String,
+    /// NOTAM number as written, `MM/NNN` (or `Y/NNNN` for FDC).
+    pub number: String,
+    pub location: String,
+    /// Keyword such as `RWY`, `TWY`, `NAV` or `OBST`; `None` when not a known keyword.
+    pub keyword: Option<String>,
+    pub raw: String,
+}
+
+/// Reference of a NOTAM F)/G) vertical limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerticalDatum {
+    Surface,
+    Ground,
+    Amsl,
+    Agl,
+    FlightLevel,
+    Unlimited,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerticalUnit {
+    Ft,
+    M,
+    Fl,
+}
+
+/// Parsed F) or G) limit such as `SFC`, `FL120`, `3000FT AMSL` or `500M AGL`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerticalLimit {
+    pub datum: VerticalDatum,
+    pub value: Option<u32>,
+    pub unit: Option<VerticalUnit>,
+    /// Height or altitude in feet above the datum; flight levels as hundreds of feet,
+    /// `0` for `SFC`/`GND`, `None` for `UNL`.
+    pub feet: Option<u32>,
+    pub raw: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotamParsed {
+    pub header: Option<NotamHeader>,
+    pub faa_header: Option<Box<FaaNotamHeader>>,
+    pub dialect: Dialect,
     pub q_line: Option<String>,
+    pub qualifiers: Option<Box<QLine>>,
+    pub period: Option<NotamPeriod>,
+    pub schedule: Option<NotamSchedule>,
+    pub lower_limit: Option<VerticalLimit>,
+    pub upper_limit: Option<VerticalLimit>,
     pub a: Option<String>,
     pub b: Option<String>,
     pub c: Option<String>,
@@ -204,29 +775,46 @@ pub enum ParsedMessage {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TafNormalized {
     pub station: Option<String>,
+    pub dialect: Dialect,
     pub issue_time: Option<UtcTime>,
     pub validity: Option<ValidityPeriod>,
     pub wind: Option<WindNormalized>,
     pub visibility_m: Option<u32>,
     pub weather: Vec<String>,
     pub clouds: Vec<CloudLayer>,
-    pub temperatures: Vec<String>,
+    pub icing: Vec<LayerForecast>,
+    pub turbulence: Vec<LayerForecast>,
+    pub wind_shear: Option<WindShearNormalized>,
+    pub qnh_inhg: Option<f32>,
+    pub qnh_hpa: Option<f32>,
+    pub temperatures: Vec<TafTemperatureForecast>,
     pub trends: Vec<TafTrendNormalized>,
+    pub rmk_raw: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TafTrendNormalized {
-    pub kind: String,
+    pub kind: TafTrendKind,
+    pub probability: Option<u8>,
+    pub from_time: Option<UtcTime>,
     pub period: Option<ValidityPeriod>,
     pub wind: Option<WindNormalized>,
     pub visibility_m: Option<u32>,
     pub weather: Vec<String>,
     pub clouds: Vec<CloudLayer>,
+    pub clears_weather: bool,
+    pub clears_clouds: bool,
+    pub icing: Vec<LayerForecast>,
+    pub turbulence: Vec<LayerForecast>,
+    pub wind_shear: Option<WindShearNormalized>,
+    pub qnh_inhg: Option<f32>,
+    pub qnh_hpa: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetarNormalized {
     pub station: Option<String>,
+    pub dialect: Dialect,
     pub issue_time: Option<UtcTime>,
     pub wind: Option<WindNormalized>,
     pub wind_variation: Option<WindVariation>,
@@ -240,17 +828,101 @@ pub struct MetarNormalized {
     pub pressure_hpa_int: Option<u16>,
     pub rmk_raw: Option<String>,
     pub rmk_tokens: Vec<String>,
+    pub remarks: Option<MetarRemarks>,
     pub trend: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NavaidKind {
+    Ils,
+    Localizer,
+    GlidePath,
+    Marker,
+    Vor,
+    Dme,
+    VorDme,
+    Ndb,
+    Tacan,
+    Vortac,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LightingKind {
+    Approach,
+    Papi,
+    Vasis,
+    RunwayEdge,
+    RunwayCentreline,
+    Threshold,
+    TouchdownZone,
+    TaxiwayCentreline,
+    TaxiwayEdge,
+    AerodromeBeacon,
+    Other,
+}
+
+/// Operationally relevant item extracted from a NOTAM's Q) code and E) text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotamRecord {
+    RunwayClosure {
+        runway: String,
+        period: Option<NotamPeriod>,
+    },
+    TaxiwayClosure {
+        taxiway: String,
+        period: Option<NotamPeriod>,
+    },
+    NavaidOutage {
+        kind: NavaidKind,
+        ident: Option<String>,
+        /// As written, e.g. `114.7MHZ` or `375KHZ`.
+        frequency: Option<String>,
+        runway: Option<String>,
+    },
+    LightingOutage {
+        kind: LightingKind,
+        runway: Option<String>,
+    },
+    Obstacle {
+        /// Obstacle type as written, e.g. `CRANE` or `TOWER`.
+        kind: Option<String>,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        elevation_ft: Option<u32>,
+        height_agl_ft: Option<u32>,
+        lighted: Option<bool>,
+    },
+}
+
+/// E) text with its abbreviations expanded, in English and Simplified Chinese.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotamExpandedText {
+    pub en: String,
+    #[serde(rename = "zh-CN")]
+    pub zh_cn: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotamNormalized {
+    pub header: Option<NotamHeader>,
+    pub faa_header: Option<Box<FaaNotamHeader>>,
+    pub dialect: Dialect,
     pub q_line: Option<String>,
+    pub qualifiers: Option<Box<QLine>>,
+    pub period: Option<NotamPeriod>,
+    pub schedule: Option<NotamSchedule>,
+    pub lower_limit: Option<VerticalLimit>,
+    pub upper_limit: Option<VerticalLimit>,
     pub a: Option<String>,
     pub b: Option<String>,
     pub c: Option<String>,
     pub d: Option<String>,
     pub e: Option<String>,
+    pub e_expanded: Option<Box<NotamExpandedText>>,
+    pub records: Vec<NotamRecord>,
     pub f: Option<String>,
     pub g: Option<String>,
 }
@@ -263,12 +935,17 @@ pub enum NormalizedMessage {
     Notam(NotamNormalized),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WindNormalized {
     pub direction_deg: Option<u16>,
     pub variable: bool,
     pub speed_kt: u16,
     pub gust_kt: Option<u16>,
+    /// Speed as reported, for groups reported in metres per second (`36004MPS`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed_mps: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gust_mps: Option<u16>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -300,7 +977,7 @@ pub fn detect_message_type(raw: &str) -> MessageType {
     if upper.starts_with("METAR ") || upper.starts_with("SPECI ") {
         return MessageType::Metar;
     }
-    if upper.contains("NOTAM") || upper.contains("Q)") {
+    if upper.contains("NOTAM") || upper.contains("Q)") || upper.contains("Q）") || upper.contains("Ｑ）") || upper.starts_with('!') {
         return MessageType::Notam;
     }
     if looks_like_metar(trimmed) {
@@ -334,11 +1011,14 @@ pub fn normalize_wind(wind: &Wind) -> WindNormalized {
         "MPS" => ((gust as f32) * 1.94384).round() as u16,
         _ => gust,
     });
+    let mps = wind.unit == "MPS";
     WindNormalized {
         direction_deg: wind.direction_deg,
         variable: wind.variable,
         speed_kt,
         gust_kt,
+        speed_mps: mps.then_some(wind.speed),
+        gust_mps: wind.gust.filter(|_| mps),
     }
 }
 
@@ -351,7 +1031,11 @@ pub fn visibility_to_meters(visibility: &Visibility) -> Option<u32> {
                 Some(visibility.distance)
             }
         }
-        "SM" => Some((visibility.distance as f32 * 1609.34).round() as u32),
+        "SM" => Some(
+            visibility
+                .distance_m
+                .unwrap_or_else(|| (visibility.distance as f32 * 1609.34).round() as u32),
+        ),
         _ => None,
     }
 }
@@ -375,6 +1059,22 @@ pub fn describe_wind(wind: &WindNormalized) -> String {
     }
 }
 
+/// Wind as `describe_wind`, led by the reported metres per second for dialects that
+/// explain wind so, e.g. `360° 4m/s（8kt）`. Winds reported in knots are left in knots.
+pub fn describe_wind_for_dialect(wind: &WindNormalized, dialect: Dialect) -> String {
+    let Some(speed) = wind.speed_mps.filter(|_| dialect.explains_wind_in_mps()) else {
+        return describe_wind(wind);
+    };
+    let direction = wind.direction_deg.map_or("VRB".to_string(), |dir| format!("{}°", dir));
+    match (wind.gust_mps, wind.gust_kt) {
+        (Some(gust), Some(gust_kt)) => format!(
+            "{} {}m/s gust {}m/s（{}kt gust {}kt）",
+            direction, speed, gust, wind.speed_kt, gust_kt
+        ),
+        _ => format!("{} {}m/s（{}kt）", direction, speed, wind.speed_kt),
+    }
+}
+
 pub fn describe_visibility_meters(distance_m: u32) -> String {
     if distance_m >= 10000 {
         "10km or more".to_string()
@@ -397,12 +1097,51 @@ pub fn describe_clouds(clouds: &[CloudLayer]) -> String {
     parts.join(", ")
 }
 
+/// FAA flight category derived from ceiling and visibility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FlightCategory {
+    Lifr,
+    Ifr,
+    Mvfr,
+    Vfr,
+}
+
+/// Height of the lowest `BKN`, `OVC` or `VV` layer, if any.
+pub fn ceiling_ft(clouds: &[CloudLayer]) -> Option<u16> {
+    clouds
+        .iter()
+        .filter(|layer| matches!(layer.amount.as_str(), "BKN" | "OVC" | "VV"))
+        .filter_map(|layer| layer.height_ft)
+        .min()
+}
+
+/// Flight category from visibility in meters and ceiling in feet. A missing ceiling
+/// counts as unlimited; returns `None` only when visibility is unknown too.
+pub fn flight_category(visibility_m: Option<u32>, ceiling: Option<u16>) -> Option<FlightCategory> {
+    if visibility_m.is_none() && ceiling.is_none() {
+        return None;
+    }
+    let vis = visibility_m.unwrap_or(u32::MAX);
+    let ceiling = ceiling.unwrap_or(u16::MAX);
+    let category = if ceiling < 500 || vis < 1609 {
+        FlightCategory::Lifr
+    } else if ceiling < 1000 || vis < 4828 {
+        FlightCategory::Ifr
+    } else if ceiling <= 3000 || vis <= 8047 {
+        FlightCategory::Mvfr
+    } else {
+        FlightCategory::Vfr
+    };
+    Some(category)
+}
+
 pub fn altimeter_to_inhg(value: u16) -> f32 {
     (value as f32) / 100.0
 }
 
 pub fn inhg_to_hpa(inhg: f32) -> f32 {
-    let hpa = inhg * 33.8638866667_f32;
+    let hpa = inhg * 33.863_887_f32;
     (hpa * 10.0).round() / 10.0
 }
 
@@ -416,18 +1155,13 @@ pub fn issue(code: &str, message: impl Into<String>, token: Option<String>) -> I
 }
 
 pub fn issue_from_legacy(message: &str) -> Issue {
-    let token_prefix = "Unrecognized token: ";
-    if message.starts_with(token_prefix) {
-        return issue(
-            "unknown_token",
-            message.to_string(),
-            Some(message[token_prefix.len()..].to_string()),
-        );
+    if let Some(token) = message.strip_prefix("Unrecognized token: ") {
+        return issue("unknown_token", message.to_string(), Some(token.to_string()));
     }
     issue("warning", message.to_string(), None)
 }
 
-pub fn sort_issues(issues: &mut Vec<Issue>) {
+pub fn sort_issues(issues: &mut [Issue]) {
     issues.sort_by(|a, b| {
         let token_a = a.token.as_deref().unwrap_or("");
         let token_b = b.token.as_deref().unwrap_or("");
@@ -436,7 +1170,7 @@ pub fn sort_issues(issues: &mut Vec<Issue>) {
     });
 }
 
-pub fn finalize_issues(warnings: &mut Vec<Issue>, errors: &mut Vec<Issue>) -> (Option<Vec<String>>, Option<Vec<String>>) {
+pub fn finalize_issues(warnings: &mut [Issue], errors: &mut [Issue]) -> (Option<Vec<String>>, Option<Vec<String>>) {
     sort_issues(warnings);
     sort_issues(errors);
     let warnings_legacy = if warnings.is_empty() {
@@ -5,7 +5,7 @@ use nom::combinator::{map, opt, recognize};
 use nom::sequence::{pair, preceded};
 use nom::IResult;
 
-use crate::{UtcTime, ValidityPeriod, Visibility, Wind};
+use crate::{LayerForecast, TafTemperatureForecast, TafTemperatureKind, UtcTime, ValidityPeriod, Visibility, Wind, WindShear};
 
 fn parse_u8(input: &str, len: usize) -> IResult<&str, u8> {
     map(take_while_m_n(len, len, |c: char| c.is_ascii_digit()), |s: &str| {
@@ -84,39 +84,73 @@ pub fn parse_visibility_meters(input: &str) -> IResult<&str, Visibility> {
             distance: s.parse::<u32>().unwrap_or(0),
             unit: "M".to_string(),
             raw: s.to_string(),
+            distance_m: None,
         }
     })(input)
 }
 
 pub fn parse_visibility_sm(input: &str) -> IResult<&str, Visibility> {
+    let (input, modifier) = opt(alt((char('P'), char('M'))))(input)?;
     let (input, value) = recognize(pair(
         take_while_m_n(1, 2, |c: char| c.is_ascii_digit()),
         opt(pair(char('/'), take_while_m_n(1, 2, |c: char| c.is_ascii_digit()))),
     ))(input)?;
     let (input, _) = tag("SM")(input)?;
-    let distance = if value.contains('/') {
+    let miles = if value.contains('/') {
         let parts: Vec<&str> = value.split('/').collect();
         if parts.len() == 2 {
             let num = parts[0].parse::<f32>().unwrap_or(0.0);
             let den = parts[1].parse::<f32>().unwrap_or(1.0);
-            (num / den).round() as u32
+            num / den
         } else {
-            0
+            0.0
         }
     } else {
-        value.parse::<u32>().unwrap_or(0)
+        value.parse::<f32>().unwrap_or(0.0)
     };
 
     Ok((
         input,
         Visibility {
-            distance,
+            distance: miles.round() as u32,
             unit: "SM".to_string(),
-            raw: format!("{}SM", value),
+            raw: match modifier {
+                Some(prefix) => format!("{}{}SM", prefix, value),
+                None => format!("{}SM", value),
+            },
+            distance_m: Some(statute_miles_to_meters(miles)),
         },
     ))
 }
 
+/// Combines a whole-mile token with the fraction that follows it, e.g. `1` `1/2SM`.
+pub fn parse_visibility_sm_mixed(whole: &str, fraction: &str) -> Option<Visibility> {
+    if whole.is_empty() || whole.len() > 2 || !whole.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (rest, _) = parse_visibility_sm(fraction).ok()?;
+    if !rest.is_empty() || !fraction.contains('/') || fraction.starts_with(['P', 'M']) {
+        return None;
+    }
+    let parts: Vec<&str> = fraction.trim_end_matches("SM").split('/').collect();
+    let num = parts.first()?.parse::<f32>().ok()?;
+    let den = parts.get(1)?.parse::<f32>().ok()?;
+    if den == 0.0 {
+        return None;
+    }
+    let miles = whole.parse::<f32>().ok()? + num / den;
+    Some(Visibility {
+        distance: miles.round() as u32,
+        unit: "SM".to_string(),
+        raw: format!("{} {}", whole, fraction),
+        distance_m: Some(statute_miles_to_meters(miles)),
+    })
+}
+
+fn statute_miles_to_meters(miles: f32) -> u32 {
+    (miles * 1609.34).round() as u32
+}
+
 pub fn parse_temperature_pair(input: &str) -> IResult<&str, (i16, i16)> {
     let (input, temp) = parse_signed_temp(input)?;
     let (input, _) = char('/')(input)?;
@@ -124,6 +158,30 @@ pub fn parse_temperature_pair(input: &str) -> IResult<&str, (i16, i16)> {
     Ok((input, (temp, dew)))
 }
 
+pub fn parse_taf_temperature(input: &str) -> IResult<&str, TafTemperatureForecast> {
+    let (rest, kind) = preceded(
+        char('T'),
+        alt((
+            map(char('X'), |_| TafTemperatureKind::Max),
+            map(char('N'), |_| TafTemperatureKind::Min),
+        )),
+    )(input)?;
+    let (rest, value_c) = parse_signed_temp(rest)?;
+    let (rest, _) = char('/')(rest)?;
+    let (rest, day) = parse_u8(rest, 2)?;
+    let (rest, hour) = parse_u8(rest, 2)?;
+    let (rest, _) = char('Z')(rest)?;
+    Ok((
+        rest,
+        TafTemperatureForecast {
+            kind,
+            value_c,
+            at: UtcTime { day, hour, minute: 0 },
+            raw: input[..input.len() - rest.len()].to_string(),
+        },
+    ))
+}
+
 fn parse_signed_temp(input: &str) -> IResult<&str, i16> {
     let (input, sign) = opt(char('M'))(input)?;
     let (input, value) = map(take_while_m_n(2, 2, |c: char| c.is_ascii_digit()), |s: &str| {
@@ -149,8 +207,48 @@ pub fn parse_pressure_altimeter(input: &str) -> IResult<&str, u16> {
     Ok((input, value))
 }
 
+/// Parses `6IchhhT` (icing, `indicator` `'6'`) or `5BhhhT` (turbulence, `indicator` `'5'`).
+pub fn parse_layer_forecast(input: &str, indicator: char) -> IResult<&str, LayerForecast> {
+    let (rest, _) = char(indicator)(input)?;
+    let (rest, code) = parse_u8(rest, 1)?;
+    let (rest, base) = parse_u16(rest, 3)?;
+    let (rest, thickness) = parse_u8(rest, 1)?;
+    Ok((
+        rest,
+        LayerForecast {
+            code,
+            base_ft: base as u32 * 100,
+            thickness_ft: thickness as u32 * 1000,
+            raw: input[..input.len() - rest.len()].to_string(),
+        },
+    ))
+}
+
+pub fn parse_wind_shear(input: &str) -> IResult<&str, WindShear> {
+    let (rest, _) = tag("WS")(input)?;
+    let (rest, height) = parse_u16(rest, 3)?;
+    let (rest, _) = char('/')(rest)?;
+    let (rest, wind) = parse_wind(rest)?;
+    Ok((
+        rest,
+        WindShear {
+            height_ft: height as u32 * 100,
+            wind,
+            raw: input[..input.len() - rest.len()].to_string(),
+        },
+    ))
+}
+
+/// Parses the forecast minimum altimeter group `QNHddddINS`, returning hundredths of inHg.
+pub fn parse_qnh_inches(input: &str) -> IResult<&str, u16> {
+    let (rest, _) = tag("QNH")(input)?;
+    let (rest, value) = parse_u16(rest, 4)?;
+    let (rest, _) = tag("INS")(rest)?;
+    Ok((rest, value))
+}
+
 pub fn parse_cloud_layer(input: &str) -> IResult<&str, (String, Option<u16>, Option<String>)> {
-    let (input, amount) = alt((tag("FEW"), tag("SCT"), tag("BKN"), tag("OVC"), tag("NSC"), tag("SKC")))(input)?;
+    let (input, amount) = alt((tag("FEW"), tag("SCT"), tag("BKN"), tag("OVC"), tag("NSC"), tag("SKC"), tag("VV")))(input)?;
     let (input, height) = opt(map(take_while_m_n(3, 3, |c: char| c.is_ascii_digit()), |s: &str| {
         s.parse::<u16>().unwrap_or(0) * 100
     }))(input)?;
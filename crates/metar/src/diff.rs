@@ -0,0 +1,470 @@
+//! Significant changes between two consecutive METARs and whether they meet the
+//! SPECI criteria of ICAO Annex 3 (4.4.2) or the FAA (FMH-1 / AIM 7-1).
+//!
+//! Only criteria that can be judged from the report itself are evaluated; station
+//! specific thresholds such as approach minima and RVR limits are out of scope.
+//! Reports from different stations are not compared: the diff is empty and carries a
+//! `station_mismatch` warning.
+
+use std::collections::BTreeMap;
+
+use aviation_wx_core::{
+    ceiling_ft, describe_visibility_meters, describe_wind, flight_category, issue, Dialect, Issue, MetarNormalized,
+    UtcTime, ValidityPeriod,
+};
+use serde::{Deserialize, Serialize};
+
+const ICAO_VISIBILITY_M: [u32; 4] = [800, 1500, 3000, 5000];
+const FAA_VISIBILITY_M: [u32; 4] = [805, 1609, 3219, 4828];
+const ICAO_CEILING_FT: [u16; 5] = [100, 200, 500, 1000, 1500];
+const FAA_CEILING_FT: [u16; 4] = [500, 1000, 1500, 3000];
+const PRECIPITATION: [&str; 8] = ["DZ", "RA", "SN", "SG", "PL", "GR", "GS", "UP"];
+
+/// Which set of SPECI criteria to apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeciRules {
+    Icao,
+    Faa,
+}
+
+impl SpeciRules {
+    pub fn for_dialect(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::Faa => SpeciRules::Faa,
+            _ => SpeciRules::Icao,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetarChangeKind {
+    WindShift,
+    WindSpeed,
+    Gust,
+    Visibility,
+    Ceiling,
+    LowCloud,
+    WeatherOnset,
+    WeatherCessation,
+    WeatherIntensity,
+    FlightCategory,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetarChange {
+    pub kind: MetarChangeKind,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// Whether this change alone meets a SPECI criterion.
+    pub speci: bool,
+    /// The criterion that was met, when `speci` is set.
+    pub criterion: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetarDiff {
+    pub station: Option<String>,
+    pub rules: SpeciRules,
+    pub before_time: Option<UtcTime>,
+    pub after_time: Option<UtcTime>,
+    pub changes: Vec<MetarChange>,
+    pub speci_required: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Issue>,
+}
+
+/// Compares two METARs using the SPECI rules of the newer report's dialect.
+pub fn diff_metar(previous: &MetarNormalized, current: &MetarNormalized) -> MetarDiff {
+    diff_metar_with_rules(previous, current, SpeciRules::for_dialect(current.dialect))
+}
+
+pub fn diff_metar_with_rules(previous: &MetarNormalized, current: &MetarNormalized, rules: SpeciRules) -> MetarDiff {
+    if let (Some(before), Some(after)) = (&previous.station, &current.station) {
+        if before != after {
+            return MetarDiff {
+                station: Some(after.clone()),
+                rules,
+                before_time: previous.issue_time.clone(),
+                after_time: current.issue_time.clone(),
+                changes: Vec::new(),
+                speci_required: false,
+                warnings: vec![issue(
+                    "station_mismatch",
+                    format!("Reports are from different stations ({} and {}); not compared", before, after),
+                    Some(before.clone()),
+                )],
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    wind_changes(previous, current, rules, &mut changes);
+    visibility_change(previous, current, rules, &mut changes);
+    cloud_changes(previous, current, rules, &mut changes);
+    weather_changes(previous, current, rules, &mut changes);
+
+    let before_category = flight_category(previous.visibility_m, ceiling_ft(&previous.clouds));
+    let after_category = flight_category(current.visibility_m, ceiling_ft(&current.clouds));
+    if before_category != after_category {
+        changes.push(change(
+            MetarChangeKind::FlightCategory,
+            before_category.map(|c| format!("{:?}", c).to_uppercase()),
+            after_category.map(|c| format!("{:?}", c).to_uppercase()),
+            None,
+        ));
+    }
+
+    MetarDiff {
+        station: current.station.clone().or_else(|| previous.station.clone()),
+        rules,
+        before_time: previous.issue_time.clone(),
+        after_time: current.issue_time.clone(),
+        speci_required: changes.iter().any(|item| item.speci),
+        changes,
+        warnings: Vec::new(),
+    }
+}
+
+fn change(kind: MetarChangeKind, before: Option<String>, after: Option<String>, criterion: Option<String>) -> MetarChange {
+    MetarChange {
+        kind,
+        before,
+        after,
+        speci: criterion.is_some(),
+        criterion,
+    }
+}
+
+fn wind_changes(previous: &MetarNormalized, current: &MetarNormalized, rules: SpeciRules, changes: &mut Vec<MetarChange>) {
+    let (Some(before), Some(after)) = (&previous.wind, &current.wind) else {
+        return;
+    };
+    let describe = |wind| Some(describe_wind(wind));
+
+    if let (Some(a), Some(b), false, false) = (before.direction_deg, after.direction_deg, before.variable, after.variable) {
+        let diff = (a as i32 - b as i32).rem_euclid(360);
+        let shift = diff.min(360 - diff);
+        if shift >= 45 {
+            let criterion = match rules {
+                SpeciRules::Icao if shift >= 60 && before.speed_kt.max(after.speed_kt) >= 10 => {
+                    Some("wind direction changed by 60° or more with mean speed 10 kt or more".to_string())
+                }
+                SpeciRules::Faa
+                    if before.speed_kt.min(after.speed_kt) >= 10
+                        && elapsed_minutes(previous, current).is_none_or(|minutes| minutes < 15) =>
+                {
+                    Some("wind direction changed by 45° or more in less than 15 minutes with speed 10 kt or more".to_string())
+                }
+                _ => None,
+            };
+            changes.push(change(MetarChangeKind::WindShift, describe(before), describe(after), criterion));
+        }
+    }
+
+    if before.speed_kt.abs_diff(after.speed_kt) >= 10 {
+        let criterion = (rules == SpeciRules::Icao).then(|| "mean wind speed changed by 10 kt or more".to_string());
+        changes.push(change(MetarChangeKind::WindSpeed, describe(before), describe(after), criterion));
+    }
+
+    let spread = |gust: Option<u16>, speed: u16| gust.map_or(0, |gust| gust.saturating_sub(speed));
+    let before_spread = spread(before.gust_kt, before.speed_kt);
+    let after_spread = spread(after.gust_kt, after.speed_kt);
+    if before_spread.abs_diff(after_spread) >= 10 {
+        let criterion = (rules == SpeciRules::Icao
+            && after_spread > before_spread
+            && before.speed_kt.max(after.speed_kt) >= 15)
+            .then(|| "gusts increased by 10 kt or more with mean speed 15 kt or more".to_string());
+        changes.push(change(MetarChangeKind::Gust, describe(before), describe(after), criterion));
+    }
+}
+
+fn elapsed_minutes(previous: &MetarNormalized, current: &MetarNormalized) -> Option<i64> {
+    let period = ValidityPeriod {
+        from: previous.issue_time.clone()?,
+        to: current.issue_time.clone()?,
+    };
+    Some(period.duration_minutes())
+}
+
+fn visibility_change(previous: &MetarNormalized, current: &MetarNormalized, rules: SpeciRules, changes: &mut Vec<MetarChange>) {
+    let (Some(before), Some(after)) = (previous.visibility_m, current.visibility_m) else {
+        return;
+    };
+    let thresholds: &[u32] = match rules {
+        SpeciRules::Icao => &ICAO_VISIBILITY_M,
+        SpeciRules::Faa => &FAA_VISIBILITY_M,
+    };
+    let crossed: Vec<String> = thresholds
+        .iter()
+        .filter(|threshold| (before < **threshold) != (after < **threshold))
+        .map(|threshold| format!("{} m", threshold))
+        .collect();
+    if !crossed.is_empty() {
+        changes.push(change(
+            MetarChangeKind::Visibility,
+            Some(describe_visibility_meters(before)),
+            Some(describe_visibility_meters(after)),
+            Some(format!("visibility passed {}", crossed.join(", "))),
+        ));
+    }
+}
+
+fn cloud_changes(previous: &MetarNormalized, current: &MetarNormalized, rules: SpeciRules, changes: &mut Vec<MetarChange>) {
+    let before = ceiling_ft(&previous.clouds);
+    let after = ceiling_ft(&current.clouds);
+    let thresholds: &[u16] = match rules {
+        SpeciRules::Icao => &ICAO_CEILING_FT,
+        SpeciRules::Faa => &FAA_CEILING_FT,
+    };
+    let below = |ceiling: Option<u16>, threshold: u16| ceiling.is_some_and(|height| height < threshold);
+    let crossed: Vec<String> = thresholds
+        .iter()
+        .filter(|threshold| below(before, **threshold) != below(after, **threshold))
+        .map(|threshold| format!("{} ft", threshold))
+        .collect();
+    let describe = |ceiling: Option<u16>| Some(ceiling.map_or_else(|| "none".to_string(), |height| format!("{}ft", height)));
+    if !crossed.is_empty() {
+        changes.push(change(
+            MetarChangeKind::Ceiling,
+            describe(before),
+            describe(after),
+            Some(format!("ceiling passed {}", crossed.join(", "))),
+        ));
+    }
+
+    if rules == SpeciRules::Faa {
+        let lowest = |metar: &MetarNormalized| metar.clouds.iter().filter_map(|layer| layer.height_ft).min();
+        let (before_low, after_low) = (lowest(previous), lowest(current));
+        if before_low.is_none_or(|height| height >= 1000) && after_low.is_some_and(|height| height < 1000) {
+            changes.push(change(
+                MetarChangeKind::LowCloud,
+                before_low.map(|height| format!("{}ft", height)),
+                after_low.map(|height| format!("{}ft", height)),
+                Some("cloud layer below 1000 ft appeared".to_string()),
+            ));
+        }
+    }
+}
+
+fn weather_changes(previous: &MetarNormalized, current: &MetarNormalized, rules: SpeciRules, changes: &mut Vec<MetarChange>) {
+    let before = phenomena(&previous.weather);
+    let after = phenomena(&current.weather);
+    let mut codes: Vec<&String> = before.keys().chain(after.keys()).collect();
+    codes.sort();
+    codes.dedup();
+
+    for code in codes {
+        let (old, new) = (before.get(code), after.get(code));
+        let kind = match (old, new) {
+            (None, Some(_)) => MetarChangeKind::WeatherOnset,
+            (Some(_), None) => MetarChangeKind::WeatherCessation,
+            (Some(a), Some(b)) if a != b => MetarChangeKind::WeatherIntensity,
+            _ => continue,
+        };
+        let label = |intensity: Option<&&str>| intensity.map(|prefix| format!("{}{}", prefix, code));
+        let criterion = weather_criterion(rules, code, kind, old.copied(), new.copied())
+            .then(|| format!("{} {}", code, kind_verb(kind)));
+        changes.push(change(kind, label(old), label(new), criterion));
+    }
+}
+
+fn kind_verb(kind: MetarChangeKind) -> &'static str {
+    match kind {
+        MetarChangeKind::WeatherOnset => "began",
+        MetarChangeKind::WeatherCessation => "ended",
+        _ => "changed intensity",
+    }
+}
+
+fn weather_criterion(rules: SpeciRules, code: &str, kind: MetarChangeKind, old: Option<&str>, new: Option<&str>) -> bool {
+    let freezing = code.starts_with("FZ") && code != "FZFG";
+    let intensity_counts = kind != MetarChangeKind::WeatherIntensity;
+    match rules {
+        SpeciRules::Icao => {
+            if freezing || matches!(code, "TS" | "DS" | "SS" | "FC") {
+                return true;
+            }
+            if matches!(code, "FZFG" | "SQ") || code.starts_with("DR") || code.starts_with("BL") {
+                return intensity_counts;
+            }
+            // Moderate or heavy precipitation, including showers.
+            PRECIPITATION.contains(&code) && (old.is_some_and(|i| i != "-") || new.is_some_and(|i| i != "-"))
+        }
+        SpeciRules::Faa => {
+            if freezing || code == "PL" {
+                return true;
+            }
+            matches!(code, "TS" | "GR" | "FC" | "SQ") && intensity_counts
+        }
+    }
+}
+
+/// Splits present-weather groups into phenomena keyed by code (`TS`, `RA`, `FZRA`,
+/// `BLSN`, ...) with their intensity prefix. Vicinity and recent weather are ignored.
+fn phenomena(weather: &[String]) -> BTreeMap<String, &'static str> {
+    let mut found = BTreeMap::new();
+    for token in weather {
+        if token.starts_with("VC") || token.starts_with("RE") {
+            continue;
+        }
+        let (intensity, body) = match token.as_bytes().first() {
+            Some(b'+') => ("+", &token[1..]),
+            Some(b'-') => ("-", &token[1..]),
+            _ => ("", token.as_str()),
+        };
+        let (descriptor, rest) = match body.get(..2) {
+            Some(d @ ("MI" | "PR" | "BC" | "DR" | "BL" | "SH" | "TS" | "FZ")) => (d, &body[2..]),
+            _ => ("", body),
+        };
+        if descriptor == "TS" {
+            found.insert("TS".to_string(), intensity);
+        }
+        for chunk in rest.as_bytes().chunks(2).filter_map(|c| std::str::from_utf8(c).ok()) {
+            let code = match descriptor {
+                "FZ" | "DR" | "BL" | "MI" | "PR" | "BC" => format!("{}{}", descriptor, chunk),
+                _ => chunk.to_string(),
+            };
+            found.insert(code, intensity);
+        }
+    }
+    found
+}
+
+/// Human-readable summary of `diff` for change notifications.
+pub fn describe_metar_diff(diff: &MetarDiff, lang: &str) -> String {
+    if lang != "zh-CN" {
+        return "Translation only supports zh-CN for now.".to_string();
+    }
+
+    let mut parts = Vec::new();
+    if let Some(station) = &diff.station {
+        parts.push(format!("台站 {}", station));
+    }
+    if diff.warnings.iter().any(|warning| warning.code == "station_mismatch") {
+        parts.push("两份报文来自不同台站，未比较".to_string());
+        return parts.join("，");
+    }
+    let rules = match diff.rules {
+        SpeciRules::Icao => "ICAO",
+        SpeciRules::Faa => "FAA",
+    };
+    parts.push(if diff.speci_required {
+        format!("满足 {} 特选报（SPECI）标准", rules)
+    } else {
+        format!("未达到 {} 特选报（SPECI）标准", rules)
+    });
+    if diff.changes.is_empty() {
+        parts.push("无显著变化".to_string());
+        return parts.join("，");
+    }
+
+    let lines: Vec<String> = diff
+        .changes
+        .iter()
+        .map(|item| {
+            let label = match item.kind {
+                MetarChangeKind::WindShift => "风向转变",
+                MetarChangeKind::WindSpeed => "风速变化",
+                MetarChangeKind::Gust => "阵风变化",
+                MetarChangeKind::Visibility => "能见度变化",
+                MetarChangeKind::Ceiling => "云底高变化",
+                MetarChangeKind::LowCloud => "出现 1000ft 以下云层",
+                MetarChangeKind::WeatherOnset => "天气开始",
+                MetarChangeKind::WeatherCessation => "天气结束",
+                MetarChangeKind::WeatherIntensity => "天气强度变化",
+                MetarChangeKind::FlightCategory => "飞行类别变化",
+            };
+            let mut line = format!(
+                "{} {} → {}",
+                label,
+                item.before.as_deref().unwrap_or("无"),
+                item.after.as_deref().unwrap_or("无")
+            );
+            if item.speci {
+                line.push_str("（SPECI）");
+            }
+            line
+        })
+        .collect();
+    format!("{}。变化：{}", parts.join("，"), lines.join("；"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_metar, parse_metar};
+
+    fn metar(raw: &str) -> MetarNormalized {
+        normalize_metar(&parse_metar(raw).0)
+    }
+
+    fn kinds(diff: &MetarDiff) -> Vec<(MetarChangeKind, bool)> {
+        diff.changes.iter().map(|item| (item.kind, item.speci)).collect()
+    }
+
+    #[test]
+    fn icao_criteria() {
+        let before = metar("METAR EGLL 011150Z 24008KT 6000 -RA BKN016 12/10 Q1010");
+        let after = metar("METAR EGLL 011220Z 31015G30KT 2500 +TSRA BKN008CB 10/09 Q1011");
+        let diff = diff_metar(&before, &after);
+        assert_eq!(diff.rules, SpeciRules::Icao);
+        assert!(diff.speci_required);
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                (MetarChangeKind::WindShift, true),
+                (MetarChangeKind::Gust, true),
+                (MetarChangeKind::Visibility, true),
+                (MetarChangeKind::Ceiling, true),
+                (MetarChangeKind::WeatherIntensity, true),
+                (MetarChangeKind::WeatherOnset, true),
+                (MetarChangeKind::FlightCategory, false),
+            ]
+        );
+        assert_eq!(diff.changes[2].criterion.as_deref(), Some("visibility passed 3000 m, 5000 m"));
+        assert_eq!(diff.changes[3].criterion.as_deref(), Some("ceiling passed 1000 ft, 1500 ft"));
+        let summary = describe_metar_diff(&diff, "zh-CN");
+        assert!(summary.starts_with("台站 EGLL，满足 ICAO 特选报（SPECI）标准。变化："), "{}", summary);
+    }
+
+    #[test]
+    fn faa_criteria() {
+        let before = metar("METAR KJFK 011151Z 18012KT 10SM -RA FEW030 BKN250 20/10 A3001");
+        let after = metar("SPECI KJFK 011203Z 23014KT 4SM -RA BR SCT008 BKN250 19/11 A3000");
+        let diff = diff_metar(&before, &after);
+        assert_eq!(diff.rules, SpeciRules::Faa);
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                (MetarChangeKind::WindShift, true),
+                (MetarChangeKind::LowCloud, true),
+                (MetarChangeKind::WeatherOnset, false),
+                (MetarChangeKind::FlightCategory, false),
+            ]
+        );
+        assert_eq!(diff.changes[2].after.as_deref(), Some("BR"));
+    }
+
+    #[test]
+    fn quiet_change_needs_no_speci() {
+        let before = metar("METAR ZBAA 010000Z 36004MPS 9999 FEW040 10/M05 Q1025");
+        let after = metar("METAR ZBAA 010030Z 01005MPS 9000 -RA SCT040 10/M04 Q1024");
+        let diff = diff_metar(&before, &after);
+        assert!(!diff.speci_required);
+        assert_eq!(kinds(&diff), vec![(MetarChangeKind::WeatherOnset, false)]);
+    }
+
+    #[test]
+    fn different_stations_are_not_compared() {
+        let before = metar("METAR EGLL 011150Z 24008KT 6000 -RA BKN016 12/10 Q1010");
+        let after = metar("METAR LFPG 011200Z 31025KT 1500 +TSRA BKN008CB 10/09 Q1011");
+        let diff = diff_metar(&before, &after);
+        assert!(diff.changes.is_empty());
+        assert!(!diff.speci_required);
+        assert_eq!(diff.warnings.len(), 1);
+        assert_eq!(diff.warnings[0].code, "station_mismatch");
+        assert_eq!(describe_metar_diff(&diff, "zh-CN"), "台站 LFPG，两份报文来自不同台站，未比较");
+    }
+}
@@ -1,14 +1,22 @@
 ﻿use aviation_wx_core::{ finalize_issues, issue, issue_from_legacy,
-    parse, CloudLayer, DecodeResponse, DetailLevel, MessageType, MetarNormalized, MetarParsed,
-    Pressure, Rvr, TemperaturePair, Visibility,
+    parse, CloudLayer, CloudOpacity, DecodeResponse, DetailLevel, Dialect, MessageType, MetarNormalized, MetarParsed,
+    MetarRemarks, PreciseTemperature, Pressure, Rainfall, Rvr, TemperaturePair, Visibility,
 };
 use regex::Regex;
 
+pub mod diff;
+
+pub use diff::{describe_metar_diff, diff_metar, diff_metar_with_rules, MetarChange, MetarChangeKind, MetarDiff, SpeciRules};
+
 pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
+    parse_metar_with_dialect(raw, None)
+}
+
+/// Parses a METAR using `dialect`, or the dialect inferred from the station when `None`.
+pub fn parse_metar_with_dialect(raw: &str, dialect: Option<Dialect>) -> (MetarParsed, Vec<String>) {
     let mut warnings = Vec::new();
     let tokens: Vec<String> = raw
-        .replace('\n', " ")
-        .replace('\r', " ")
+        .replace(['\n', '\r'], " ")
         .split_whitespace()
         .map(|s| s.trim().to_string())
         .collect();
@@ -28,10 +36,11 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
     let mut trend = None;
     let mut rmk_raw = None;
     let mut rmk_tokens = Vec::new();
+    let mut remarks = None;
     let mut raw_tokens = Vec::new();
 
     let mut idx = 0;
-    if tokens.get(0).map(|t| t.as_str()) == Some("METAR") || tokens.get(0).map(|t| t.as_str()) == Some("SPECI") {
+    if matches!(tokens.first().map(|t| t.as_str()), Some("METAR") | Some("SPECI")) {
         idx += 1;
     }
 
@@ -50,6 +59,10 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
         }
     }
 
+    let dialect = dialect
+        .or_else(|| station.as_deref().map(Dialect::from_station))
+        .unwrap_or_default();
+
     let weather_re = Regex::new(r"^(\+|\-|VC)?[A-Z]{2,6}$").unwrap();
     let rvr_re = Regex::new(r"^R(?P<runway>\d{2}[LCR]?)/(?P<base>\d{4})(?:V(?P<vary>\d{4}))?(?P<trend>[UDN])?$")
         .unwrap();
@@ -61,6 +74,7 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
                 distance: 10000,
                 unit: "M".to_string(),
                 raw: token.clone(),
+                distance_m: None,
             });
             idx += 1;
             continue;
@@ -106,6 +120,17 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
             continue;
         }
 
+        if dialect.uses_statute_miles() {
+            if let Some(parsed) = tokens
+                .get(idx + 1)
+                .and_then(|next| parse::parse_visibility_sm_mixed(&token, next))
+            {
+                visibility = Some(parsed);
+                idx += 2;
+                continue;
+            }
+        }
+
         if parse::parse_visibility_meters(&token).is_ok() {
             let (_, parsed) = parse::parse_visibility_meters(&token).unwrap();
             visibility = Some(parsed);
@@ -132,6 +157,17 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
             continue;
         }
 
+        if token == "CLR" && dialect.accepts_clr() {
+            clouds.push(CloudLayer {
+                amount: token.clone(),
+                height_ft: None,
+                cloud_type: None,
+                raw: token.clone(),
+            });
+            idx += 1;
+            continue;
+        }
+
         if parse::parse_temperature_pair(&token).is_ok() {
             let (_, (temp, dew)) = parse::parse_temperature_pair(&token).unwrap();
             temperature = Some(TemperaturePair {
@@ -178,6 +214,13 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
             if idx + 1 < tokens.len() {
                 let tail = tokens[idx + 1..].to_vec();
                 rmk_raw = Some(tail.join(" "));
+                if dialect.uses_faa_remarks() {
+                    remarks = Some(parse_faa_remarks(&tail));
+                } else if dialect.uses_canadian_remarks() {
+                    remarks = Some(parse_canadian_remarks(&tail));
+                } else if dialect.uses_australian_remarks() {
+                    remarks = Some(parse_australian_remarks(&tail));
+                }
                 rmk_tokens = tail;
             } else {
                 rmk_raw = Some(String::new());
@@ -204,6 +247,7 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
     (
         MetarParsed {
             station,
+            dialect,
             issue_time,
             wind,
             wind_variation,
@@ -217,6 +261,7 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
             pressure,
             rmk_raw,
             rmk_tokens,
+            remarks,
             trend,
             raw_tokens,
         },
@@ -224,6 +269,98 @@ pub fn parse_metar(raw: &str) -> (MetarParsed, Vec<String>) {
     )
 }
 
+/// Decodes the FAA remark groups this crate understands; everything else is kept in `unparsed`.
+pub fn parse_faa_remarks(tokens: &[String]) -> MetarRemarks {
+    let slp_re = Regex::new(r"^SLP(?P<value>\d{3})$").unwrap();
+    let temp_re = Regex::new(r"^T(?P<ts>[01])(?P<t>\d{3})(?:(?P<ds>[01])(?P<d>\d{3}))?$").unwrap();
+    let mut remarks = MetarRemarks::default();
+
+    for token in tokens {
+        if token == "AO1" || token == "AO2" {
+            remarks.station_type = Some(token.clone());
+            continue;
+        }
+        if let Some(caps) = slp_re.captures(token) {
+            remarks.sea_level_pressure_hpa = Some(sea_level_pressure(&caps["value"]));
+            continue;
+        }
+        if let Some(caps) = temp_re.captures(token) {
+            let signed = |sign: &str, value: &str| {
+                let tenths = value.parse::<f32>().unwrap_or(0.0) / 10.0;
+                if sign == "1" {
+                    -tenths
+                } else {
+                    tenths
+                }
+            };
+            remarks.temperature_precise = Some(PreciseTemperature {
+                temperature_c: signed(&caps["ts"], &caps["t"]),
+                dewpoint_c: match (caps.name("ds"), caps.name("d")) {
+                    (Some(sign), Some(value)) => Some(signed(sign.as_str(), value.as_str())),
+                    _ => None,
+                },
+            });
+            continue;
+        }
+        remarks.unparsed.push(token.clone());
+    }
+
+    remarks
+}
+
+/// Decodes Canadian remarks: `SLPppp` and cloud opacity groups such as `SC6AC2` (stratocumulus
+/// 6 oktas, altocumulus 2 oktas). Everything else is kept in `unparsed`.
+pub fn parse_canadian_remarks(tokens: &[String]) -> MetarRemarks {
+    let slp_re = Regex::new(r"^SLP(?P<value>\d{3})$").unwrap();
+    let opacity_re = Regex::new(r"^(?:(?:TCU|ACC|CB|CU|CF|SC|SF|ST|NS|AS|AC|CS|CC|CI|FG)\d)+$").unwrap();
+    let layer_re = Regex::new(r"(?P<kind>TCU|ACC|CB|CU|CF|SC|SF|ST|NS|AS|AC|CS|CC|CI|FG)(?P<oktas>\d)").unwrap();
+    let mut remarks = MetarRemarks::default();
+
+    for token in tokens {
+        if let Some(caps) = slp_re.captures(token) {
+            remarks.sea_level_pressure_hpa = Some(sea_level_pressure(&caps["value"]));
+            continue;
+        }
+        if opacity_re.is_match(token) {
+            remarks.cloud_opacity.extend(layer_re.captures_iter(token).map(|caps| CloudOpacity {
+                cloud_type: caps["kind"].to_string(),
+                oktas: caps["oktas"].parse().unwrap_or(0),
+            }));
+            continue;
+        }
+        remarks.unparsed.push(token.clone());
+    }
+
+    remarks
+}
+
+/// Decodes the Australian rainfall group `RFxx.x/xxx.x` (mm in the last 10 minutes / since
+/// 09 local time). Everything else is kept in `unparsed`.
+pub fn parse_australian_remarks(tokens: &[String]) -> MetarRemarks {
+    let rainfall_re = Regex::new(r"^RF(?P<recent>\d{2}\.\d)/(?P<since>\d{3}\.\d)$").unwrap();
+    let mut remarks = MetarRemarks::default();
+
+    for token in tokens {
+        if let Some(caps) = rainfall_re.captures(token) {
+            remarks.rainfall = Some(Rainfall {
+                last_10_min_mm: caps["recent"].parse().unwrap_or(0.0),
+                since_9am_mm: caps["since"].parse().unwrap_or(0.0),
+            });
+            continue;
+        }
+        remarks.unparsed.push(token.clone());
+    }
+
+    remarks
+}
+
+/// `SLPppp` tenths of hPa, with the leading 9 or 10 restored.
+fn sea_level_pressure(value: &str) -> f32 {
+    let value = value.parse::<f32>().unwrap_or(0.0) / 10.0;
+    let hpa = if value < 50.0 { 1000.0 + value } else { 900.0 + value };
+    (hpa * 10.0).round() / 10.0
+}
+
 pub fn normalize_metar(parsed: &MetarParsed) -> MetarNormalized {
     let pressure_hpa_int = parsed
         .pressure_qnh
@@ -235,14 +372,13 @@ pub fn normalize_metar(parsed: &MetarParsed) -> MetarNormalized {
         .and_then(|p| if p.unit == "inHg*100" { Some(aviation_wx_core::altimeter_to_inhg(p.value)) } else { None });
     let pressure_hpa = if let Some(value) = pressure_hpa_int {
         Some(value as f32)
-    } else if let Some(inhg) = pressure_inhg {
-        Some(aviation_wx_core::inhg_to_hpa(inhg))
     } else {
-        None
+        pressure_inhg.map(aviation_wx_core::inhg_to_hpa)
     };
 
     MetarNormalized {
         station: parsed.station.clone(),
+        dialect: parsed.dialect,
         issue_time: parsed.issue_time.clone(),
         wind: parsed.wind.as_ref().map(aviation_wx_core::normalize_wind),
         wind_variation: parsed.wind_variation.clone(),
@@ -256,6 +392,7 @@ pub fn normalize_metar(parsed: &MetarParsed) -> MetarNormalized {
         pressure_hpa_int,
         rmk_raw: parsed.rmk_raw.clone(),
         rmk_tokens: parsed.rmk_tokens.clone(),
+        remarks: parsed.remarks.clone(),
         trend: parsed.trend.clone(),
     }
 }
@@ -273,7 +410,7 @@ pub fn translate_metar(normalized: &MetarNormalized, detail: DetailLevel, lang:
         parts.push(format!("发布于 {:02} 日 {:02}:{:02}Z", time.day, time.hour, time.minute));
     }
     if let Some(wind) = &normalized.wind {
-        parts.push(format!("风 {}", aviation_wx_core::describe_wind(wind)));
+        parts.push(format!("风 {}", aviation_wx_core::describe_wind_for_dialect(wind, normalized.dialect)));
     }
     if let Some(variation) = &normalized.wind_variation {
         parts.push(format!(
@@ -333,6 +470,14 @@ pub fn translate_metar(normalized: &MetarNormalized, detail: DetailLevel, lang:
             parts.push(format!("备注（RMK）：{}", truncated));
         }
     }
+    if detail == DetailLevel::Full {
+        if let Some(remarks) = &normalized.remarks {
+            let decoded = describe_remarks(remarks);
+            if !decoded.is_empty() {
+                parts.push(format!("备注解码：{}", decoded));
+            }
+        }
+    }
 
     let mut text = parts.join("，");
     if detail != DetailLevel::Brief {
@@ -345,6 +490,39 @@ pub fn translate_metar(normalized: &MetarNormalized, detail: DetailLevel, lang:
     text
 }
 
+fn describe_remarks(remarks: &MetarRemarks) -> String {
+    let mut parts = Vec::new();
+    match remarks.station_type.as_deref() {
+        Some("AO1") => parts.push("自动站（无降水识别）".to_string()),
+        Some("AO2") => parts.push("自动站（有降水识别）".to_string()),
+        _ => {}
+    }
+    if let Some(slp) = remarks.sea_level_pressure_hpa {
+        parts.push(format!("海平面气压 {:.1} hPa", slp));
+    }
+    if let Some(temp) = &remarks.temperature_precise {
+        match temp.dewpoint_c {
+            Some(dew) => parts.push(format!("精确气温 {:.1}°C 露点 {:.1}°C", temp.temperature_c, dew)),
+            None => parts.push(format!("精确气温 {:.1}°C", temp.temperature_c)),
+        }
+    }
+    if !remarks.cloud_opacity.is_empty() {
+        let layers: Vec<String> = remarks
+            .cloud_opacity
+            .iter()
+            .map(|layer| format!("{} {}/8", layer.cloud_type, layer.oktas))
+            .collect();
+        parts.push(format!("云量 {}", layers.join(" ")));
+    }
+    if let Some(rainfall) = &remarks.rainfall {
+        parts.push(format!(
+            "近 10 分钟降水 {:.1} mm，当地 09 时以来 {:.1} mm",
+            rainfall.last_10_min_mm, rainfall.since_9am_mm
+        ));
+    }
+    parts.join("，")
+}
+
 fn truncate_text(value: &str, max_chars: usize) -> String {
     let mut result = String::new();
     for (idx, ch) in value.chars().enumerate() {
@@ -358,7 +536,16 @@ fn truncate_text(value: &str, max_chars: usize) -> String {
 }
 
 pub fn decode_metar(raw: &str, detail: DetailLevel, lang: &str) -> DecodeResponse {
-    let (parsed, warnings_legacy_raw) = parse_metar(raw);
+    decode_metar_with_dialect(raw, None, detail, lang)
+}
+
+pub fn decode_metar_with_dialect(
+    raw: &str,
+    dialect: Option<Dialect>,
+    detail: DetailLevel,
+    lang: &str,
+) -> DecodeResponse {
+    let (parsed, warnings_legacy_raw) = parse_metar_with_dialect(raw, dialect);
     let normalized = normalize_metar(&parsed);
     let explain = translate_metar(&normalized, detail, lang);
 
@@ -434,5 +621,79 @@ mod tests {
         assert_eq!(rvr.vis_vary_m, Some(1200));
         assert_eq!(rvr.tendency, "down");
     }
+
+    #[test]
+    fn faa_remarks_decoded_for_us_station() {
+        let raw = "METAR KSFO 011156Z 28012KT 1 1/2SM CLR 12/08 A3012 RMK AO2 SLP132 T01220083";
+        let (parsed, warnings) = parse_metar(raw);
+        assert!(warnings.is_empty());
+        assert_eq!(parsed.dialect, Dialect::Faa);
+        assert_eq!(parsed.visibility.as_ref().map(|v| v.raw.as_str()), Some("1 1/2SM"));
+        assert_eq!(parsed.clouds[0].amount, "CLR");
+        let remarks = parsed.remarks.expect("remarks");
+        assert_eq!(remarks.station_type.as_deref(), Some("AO2"));
+        assert!(approx_eq(remarks.sea_level_pressure_hpa.unwrap(), 1013.2, 0.01));
+        let temp = remarks.temperature_precise.expect("precise temperature");
+        assert!(approx_eq(temp.temperature_c, 12.2, 0.01));
+        assert!(approx_eq(temp.dewpoint_c.unwrap(), 8.3, 0.01));
+    }
+
+    #[test]
+    fn dialect_override_disables_faa_rules() {
+        let raw = "METAR KSFO 011156Z 28012KT 10SM CLR 12/08 A3012 RMK AO2";
+        let (parsed, _) = parse_metar_with_dialect(raw, Some(Dialect::Icao));
+        assert_eq!(parsed.dialect, Dialect::Icao);
+        assert!(parsed.remarks.is_none());
+        assert!(parsed.clouds.is_empty());
+        assert_eq!(parsed.rmk_raw.as_deref(), Some("AO2"));
+    }
+
+    #[test]
+    fn canadian_and_australian_remarks_decoded() {
+        let raw = "METAR CYYZ 011200Z 27010KT 15SM FEW030 BKN120 05/M01 A3001 RMK SC2AC5 SLP168";
+        let (parsed, warnings) = parse_metar(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.dialect, Dialect::Canada);
+        let remarks = parsed.remarks.expect("canadian remarks");
+        assert!(approx_eq(remarks.sea_level_pressure_hpa.unwrap(), 1016.8, 0.01));
+        let layers: Vec<_> = remarks.cloud_opacity.iter().map(|l| (l.cloud_type.as_str(), l.oktas)).collect();
+        assert_eq!(layers, vec![("SC", 2), ("AC", 5)]);
+
+        let raw = "METAR YSSY 010000Z 18012KT 9999 -SHRA SCT025 22/17 Q1012 RMK RF00.4/012.6";
+        let (parsed, _) = parse_metar(raw);
+        assert_eq!(parsed.dialect, Dialect::Australia);
+        let rainfall = parsed.remarks.and_then(|r| r.rainfall).expect("rainfall");
+        assert!(approx_eq(rainfall.last_10_min_mm, 0.4, 0.01) && approx_eq(rainfall.since_9am_mm, 12.6, 0.01));
+    }
+
+    #[test]
+    fn caac_wind_explained_in_mps() {
+        let (parsed, _) = parse_metar("METAR ZBAA 010000Z 36004MPS CAVOK M03/M18 Q1030 NOSIG");
+        assert_eq!(parsed.dialect, Dialect::Caac);
+        let text = translate_metar(&normalize_metar(&parsed), DetailLevel::Normal, "zh-CN");
+        assert!(text.contains("风 360° 4m/s（8kt）"), "{}", text);
+
+        let (parsed, _) = parse_metar_with_dialect("METAR ZBAA 010000Z 36004MPS CAVOK M03/M18 Q1030", Some(Dialect::Icao));
+        let text = translate_metar(&normalize_metar(&parsed), DetailLevel::Normal, "zh-CN");
+        assert!(text.contains("风 360° 8kt"), "{}", text);
+
+        let (parsed, _) = parse_metar("METAR ZBAA 010000Z 02010KT CAVOK M03/M18 Q1030");
+        let text = translate_metar(&normalize_metar(&parsed), DetailLevel::Normal, "zh-CN");
+        assert!(text.contains("风 20° 10kt"), "{}", text);
+    }
+
+    #[test]
+    fn fractional_statute_miles_keep_exact_distance() {
+        let (parsed, _) = parse_metar("METAR KJFK 011151Z 04008KT 2 1/2SM BR OVC012 08/07 A2998");
+        let normalized = normalize_metar(&parsed);
+        assert_eq!(normalized.visibility_m, Some(4023));
+        assert_eq!(
+            aviation_wx_core::flight_category(normalized.visibility_m, None),
+            Some(aviation_wx_core::FlightCategory::Ifr)
+        );
+
+        let (parsed, _) = parse_metar("METAR KJFK 011151Z 04008KT 1/4SM FG VV002 08/07 A2998");
+        assert_eq!(normalize_metar(&parsed).visibility_m, Some(402));
+    }
 }
 
@@ -0,0 +1,446 @@
+//! Export of a decoded NOTAM as an AIXM 5.1 Digital NOTAM (Event schema) message.
+//!
+//! The message holds an `event:Event` whose time slice carries the NOTAM fields in
+//! `event:textNOTAM`. Circles and polygons written in E) are added as an
+//! `aixm:Airspace` TEMPDELTA linked to the event, with F)/G) (or the Q) levels) as
+//! vertical limits; for navigation warnings without E) shapes the Q) area is used.
+//! AIXM requires UUID identifiers, which a NOTAM does not carry, so the event UUID is
+//! derived (version 5) from the NOTAM number and FIR, and the airspace UUID from the
+//! event UUID; exporting the same NOTAM twice gives the same identifiers. The NOTAM
+//! number itself is only written in `event:series`, `event:number` and `event:year`.
+
+use aviation_wx_core::{
+    NotamDateTime, NotamKind, NotamNormalized, QPurpose, QScope, QTraffic, VerticalDatum, VerticalLimit, VerticalUnit,
+};
+
+use uuid::Uuid;
+
+use crate::geometry::{extract_geometry, GeoPoint, GeometryShape, GeometrySource};
+
+const NAMESPACES: &[(&str, &str)] = &[
+    ("message", "http://www.aixm.aero/schema/5.1/message"),
+    ("gml", "http://www.opengis.net/gml/3.2"),
+    ("aixm", "http://www.aixm.aero/schema/5.1"),
+    ("event", "http://www.aixm.aero/schema/5.1/event"),
+    ("xlink", "http://www.w3.org/1999/xlink"),
+];
+const SRS_NAME: &str = "urn:ogc:def:crs:EPSG::4326";
+const UUID_CODE_SPACE: &str = "urn:uuid:";
+
+struct Writer {
+    out: String,
+    depth: usize,
+}
+
+impl Writer {
+    fn line(&mut self, text: &str) {
+        self.out.push_str(&"  ".repeat(self.depth));
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.line(&format!("<{}{}>", name, format_attributes(attributes)));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{}>", name));
+    }
+
+    fn leaf(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) {
+        self.line(&format!("<{0}{1}>{2}</{0}>", name, format_attributes(attributes), escape(text)));
+    }
+
+    fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.line(&format!("<{}{}/>", name, format_attributes(attributes)));
+    }
+}
+
+/// AIXM 5.1 `message:AIXMBasicMessage` for `notam`.
+pub fn notam_to_aixm(notam: &NotamNormalized) -> String {
+    let id = event_uuid(notam);
+    let mut writer = Writer {
+        out: String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"),
+        depth: 0,
+    };
+    let mut root: Vec<(String, &str)> = NAMESPACES
+        .iter()
+        .map(|(prefix, uri)| (format!("xmlns:{}", prefix), *uri))
+        .collect();
+    let message_id = format!("uuid.{}", Uuid::new_v5(&id, b"message:AIXMBasicMessage"));
+    root.push(("gml:id".to_string(), &message_id));
+    let root: Vec<(&str, &str)> = root.iter().map(|(name, value)| (name.as_str(), *value)).collect();
+    writer.open("message:AIXMBasicMessage", &root);
+
+    writer.open("message:hasMember", &[]);
+    write_event(&mut writer, notam, id);
+    writer.close("message:hasMember");
+
+    let shapes = airspace_shapes(notam);
+    if !shapes.is_empty() {
+        writer.open("message:hasMember", &[]);
+        write_airspace(&mut writer, notam, id, &shapes);
+        writer.close("message:hasMember");
+    }
+
+    writer.close("message:AIXMBasicMessage");
+    writer.out
+}
+
+fn write_event(writer: &mut Writer, notam: &NotamNormalized, id: Uuid) {
+    writer.open("event:Event", &[("gml:id", &format!("uuid.{}", id))]);
+    writer.leaf("gml:identifier", &[("codeSpace", UUID_CODE_SPACE)], &id.to_string());
+    writer.open("event:timeSlice", &[]);
+    writer.open("event:EventTimeSlice", &[("gml:id", &format!("uuid.{}-ts1", id))]);
+    write_valid_time(writer, notam, &format!("uuid.{}-vt1", id));
+    writer.leaf("aixm:interpretation", &[], "BASELINE");
+    writer.leaf("aixm:sequenceNumber", &[], "1");
+    writer.leaf("aixm:correctionNumber", &[], "0");
+    writer.open("event:textNOTAM", &[]);
+    writer.open("event:NOTAM", &[("gml:id", &format!("uuid.{}-n1", id))]);
+    write_notam_fields(writer, notam);
+    writer.close("event:NOTAM");
+    writer.close("event:textNOTAM");
+    writer.close("event:EventTimeSlice");
+    writer.close("event:timeSlice");
+    writer.close("event:Event");
+}
+
+fn write_notam_fields(writer: &mut Writer, notam: &NotamNormalized) {
+    if let Some(header) = &notam.header {
+        writer.leaf("event:series", &[], &header.id.series);
+        writer.leaf("event:number", &[], &header.id.number.to_string());
+        writer.leaf("event:year", &[], &(2000 + u16::from(header.id.year)).to_string());
+        let kind = match header.kind {
+            NotamKind::New => "N",
+            NotamKind::Replace => "R",
+            NotamKind::Cancel => "C",
+        };
+        writer.leaf("event:type", &[], kind);
+    }
+    if let Some(q) = &notam.qualifiers {
+        writer.leaf("event:affectedFIR", &[], &q.fir);
+        writer.leaf("event:selectionCode", &[], &q.code);
+        let traffic: String = q
+            .traffic
+            .iter()
+            .map(|item| match item {
+                QTraffic::Ifr => 'I',
+                QTraffic::Vfr => 'V',
+                QTraffic::Checklist => 'K',
+            })
+            .collect();
+        let purpose: String = q
+            .purpose
+            .iter()
+            .map(|item| match item {
+                QPurpose::ImmediateAttention => 'N',
+                QPurpose::Briefing => 'B',
+                QPurpose::OperationallySignificant => 'O',
+                QPurpose::Miscellaneous => 'M',
+                QPurpose::Checklist => 'K',
+            })
+            .collect();
+        let scope: String = q
+            .scope
+            .iter()
+            .map(|item| match item {
+                QScope::Aerodrome => 'A',
+                QScope::EnRoute => 'E',
+                QScope::NavigationWarning => 'W',
+                QScope::Checklist => 'K',
+            })
+            .collect();
+        for (name, value) in [("event:traffic", traffic), ("event:purpose", purpose), ("event:scope", scope)] {
+            if !value.is_empty() {
+                writer.leaf(name, &[], &value);
+            }
+        }
+        if let Some(lower) = q.lower_fl {
+            writer.leaf("event:minimumFL", &[], &format!("{:03}", lower));
+        }
+        if let Some(upper) = q.upper_fl {
+            writer.leaf("event:maximumFL", &[], &format!("{:03}", upper));
+        }
+        if let Some(coordinates) = q.raw.split('/').nth(7).and_then(|item| item.trim().get(..11)) {
+            writer.leaf("event:coordinates", &[], coordinates);
+        }
+        if let Some(radius) = q.radius_nm {
+            writer.leaf("event:radius", &[], &format!("{:03}", radius));
+        }
+    }
+    let fields = [
+        ("event:location", &notam.a),
+        ("event:effectiveStart", &notam.b),
+        ("event:effectiveEnd", &notam.c),
+        ("event:schedule", &notam.d),
+        ("event:text", &notam.e),
+        ("event:lowerLimit", &notam.f),
+        ("event:upperLimit", &notam.g),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            // C) is written `2402102359EST` in AIXM, without the space.
+            let value = if name == "event:effectiveEnd" {
+                value.split_whitespace().collect::<String>()
+            } else {
+                value.clone()
+            };
+            writer.leaf(name, &[], &value);
+        }
+    }
+    // The Event schema places the replaced or cancelled NOTAM after the F)/G) limits.
+    if let Some(reference) = notam.header.as_ref().and_then(|header| header.references.as_ref()) {
+        writer.leaf("event:referredSeries", &[], &reference.series);
+        writer.leaf("event:referredNumber", &[], &reference.number.to_string());
+        writer.leaf("event:referredYear", &[], &(2000 + u16::from(reference.year)).to_string());
+    }
+}
+
+fn write_valid_time(writer: &mut Writer, notam: &NotamNormalized, period_id: &str) {
+    let Some(period) = &notam.period else {
+        return;
+    };
+    writer.open("gml:validTime", &[]);
+    writer.open("gml:TimePeriod", &[("gml:id", period_id)]);
+    writer.leaf("gml:beginPosition", &[], &format_time(&period.start));
+    match (&period.end, period.permanent) {
+        (Some(end), false) => writer.leaf("gml:endPosition", &[], &format_time(end)),
+        _ => writer.empty("gml:endPosition", &[("indeterminatePosition", "unknown")]),
+    }
+    writer.close("gml:TimePeriod");
+    writer.close("gml:validTime");
+}
+
+fn write_airspace(writer: &mut Writer, notam: &NotamNormalized, event: Uuid, shapes: &[GeometryShape]) {
+    let id = Uuid::new_v5(&event, b"aixm:Airspace");
+    writer.open("aixm:Airspace", &[("gml:id", &format!("uuid.{}", id))]);
+    writer.leaf("gml:identifier", &[("codeSpace", UUID_CODE_SPACE)], &id.to_string());
+    writer.open("aixm:timeSlice", &[]);
+    writer.open("aixm:AirspaceTimeSlice", &[("gml:id", &format!("uuid.{}-ts1", id))]);
+    write_valid_time(writer, notam, &format!("uuid.{}-vt1", id));
+    writer.leaf("aixm:interpretation", &[], "TEMPDELTA");
+    writer.leaf("aixm:sequenceNumber", &[], "1");
+    writer.leaf("aixm:correctionNumber", &[], "0");
+
+    for (idx, shape) in shapes.iter().enumerate() {
+        let part = idx + 1;
+        writer.open("aixm:geometryComponent", &[]);
+        writer.open("aixm:AirspaceGeometryComponent", &[("gml:id", &format!("uuid.{}-gc{}", id, part))]);
+        writer.leaf("aixm:operation", &[], if idx == 0 { "BASE" } else { "UNION" });
+        writer.open("aixm:theAirspaceVolume", &[]);
+        writer.open("aixm:AirspaceVolume", &[("gml:id", &format!("uuid.{}-av{}", id, part))]);
+        let upper = notam.upper_limit.as_ref().map(aixm_limit).or_else(|| {
+            let level = notam.qualifiers.as_ref()?.upper_fl?;
+            Some(q_level(level))
+        });
+        let lower = notam.lower_limit.as_ref().map(aixm_limit).or_else(|| {
+            let level = notam.qualifiers.as_ref()?.lower_fl?;
+            Some(q_level(level))
+        });
+        for (name, limit) in [("aixm:upperLimit", upper), ("aixm:lowerLimit", lower)] {
+            if let Some((value, uom, reference)) = limit {
+                writer.leaf(name, &[("uom", uom)], &value);
+                if let Some(reference) = reference {
+                    writer.leaf(&format!("{}Reference", name), &[], reference);
+                }
+            }
+        }
+        writer.open("aixm:horizontalProjection", &[]);
+        writer.open("aixm:Surface", &[("gml:id", &format!("uuid.{}-s{}", id, part)), ("srsName", SRS_NAME)]);
+        writer.open("gml:patches", &[]);
+        writer.open("gml:PolygonPatch", &[]);
+        writer.open("gml:exterior", &[]);
+        writer.open("gml:Ring", &[]);
+        writer.open("gml:curveMember", &[]);
+        writer.open("gml:Curve", &[("gml:id", &format!("uuid.{}-c{}", id, part))]);
+        writer.open("gml:segments", &[]);
+        match shape {
+            GeometryShape::Circle { centre, radius_nm } => {
+                writer.open("gml:CircleByCenterPoint", &[("numArc", "1")]);
+                writer.leaf("gml:pos", &[], &format_position(centre));
+                writer.leaf("gml:radius", &[("uom", "[nmi_i]")], &format_number(*radius_nm));
+                writer.close("gml:CircleByCenterPoint");
+            }
+            GeometryShape::Polygon { points } => {
+                let ring: Vec<String> = points.iter().chain(points.first()).map(format_position).collect();
+                writer.open("gml:GeodesicString", &[]);
+                writer.leaf("gml:posList", &[], &ring.join(" "));
+                writer.close("gml:GeodesicString");
+            }
+            GeometryShape::Point { .. } => {}
+        }
+        writer.close("gml:segments");
+        writer.close("gml:Curve");
+        writer.close("gml:curveMember");
+        writer.close("gml:Ring");
+        writer.close("gml:exterior");
+        writer.close("gml:PolygonPatch");
+        writer.close("gml:patches");
+        writer.close("aixm:Surface");
+        writer.close("aixm:horizontalProjection");
+        writer.close("aixm:AirspaceVolume");
+        writer.close("aixm:theAirspaceVolume");
+        writer.close("aixm:AirspaceGeometryComponent");
+        writer.close("aixm:geometryComponent");
+    }
+
+    writer.open("aixm:extension", &[]);
+    writer.open("event:AirspaceExtension", &[("gml:id", &format!("uuid.{}-x1", id))]);
+    writer.empty("event:theEvent", &[("xlink:href", &format!("urn:uuid:{}", event))]);
+    writer.close("event:AirspaceExtension");
+    writer.close("aixm:extension");
+    writer.close("aixm:AirspaceTimeSlice");
+    writer.close("aixm:timeSlice");
+    writer.close("aixm:Airspace");
+}
+
+/// Circles and polygons of E), or the Q) circle of a navigation warning without them.
+fn airspace_shapes(notam: &NotamNormalized) -> Vec<GeometryShape> {
+    let geometries = extract_geometry(notam);
+    let area = |source: GeometrySource| {
+        geometries
+            .iter()
+            .filter(move |geometry| geometry.source == source)
+            .filter(|geometry| !matches!(geometry.shape, GeometryShape::Point { .. }))
+            .map(|geometry| geometry.shape.clone())
+            .collect::<Vec<_>>()
+    };
+    let text = area(GeometrySource::Text);
+    let warning = notam
+        .qualifiers
+        .as_ref()
+        .is_some_and(|q| q.scope.contains(&QScope::NavigationWarning));
+    if text.is_empty() && warning {
+        area(GeometrySource::QLine)
+    } else {
+        text
+    }
+}
+
+/// AIXM value, unit of measurement and reference of a F)/G) limit.
+fn aixm_limit(limit: &VerticalLimit) -> (String, &'static str, Option<&'static str>) {
+    let unit = match limit.unit {
+        Some(VerticalUnit::M) => "M",
+        _ => "FT",
+    };
+    let value = limit.value.unwrap_or_default().to_string();
+    match limit.datum {
+        VerticalDatum::Surface | VerticalDatum::Ground => ("GND".to_string(), "OTHER", Some("SFC")),
+        VerticalDatum::Unlimited => ("UNL".to_string(), "OTHER", None),
+        VerticalDatum::FlightLevel => (value, "FL", Some("STD")),
+        VerticalDatum::Amsl => (value, unit, Some("MSL")),
+        VerticalDatum::Agl => (value, unit, Some("SFC")),
+    }
+}
+
+/// Q) lower or upper flight level; `000` is the ground and `999` unlimited.
+fn q_level(level: u16) -> (String, &'static str, Option<&'static str>) {
+    match level {
+        0 => ("GND".to_string(), "OTHER", Some("SFC")),
+        999 => ("UNL".to_string(), "OTHER", None),
+        level => (level.to_string(), "FL", Some("STD")),
+    }
+}
+
+/// Version 5 UUID (URL namespace) of `urn:x-notam:<number>:<FIR>`, e.g.
+/// `urn:x-notam:A1234/24:ZSHA`, or `urn:x-notam:JFK 01/123:` for FAA domestic NOTAMs
+/// without Q).
+fn event_uuid(notam: &NotamNormalized) -> Uuid {
+    let number = match (&notam.header, &notam.faa_header) {
+        (Some(header), _) => header.id.to_string(),
+        (None, Some(faa)) => format!("{} {}", faa.accountability, faa.number),
+        (None, None) => "UNKNOWN".to_string(),
+    };
+    let fir = notam.qualifiers.as_ref().map_or("", |q| q.fir.as_str());
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, format!("urn:x-notam:{}:{}", number, fir).as_bytes())
+}
+
+fn format_time(time: &NotamDateTime) -> String {
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:00Z",
+        time.year, time.month, time.day, time.hour, time.minute
+    )
+}
+
+/// `latitude longitude` as required by EPSG:4326 axis order.
+fn format_position(point: &GeoPoint) -> String {
+    format!("{} {}", format_number(point.latitude), format_number(point.longitude))
+}
+
+fn format_number(value: f64) -> String {
+    let text = format!("{:.6}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn format_attributes(attributes: &[(&str, &str)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, escape(value)))
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_notam, parse_notam};
+
+    fn export(raw: &str) -> String {
+        notam_to_aixm(&normalize_notam(&parse_notam(raw).0))
+    }
+
+    #[test]
+    fn writes_event_with_notam_fields() {
+        let xml = export(
+            "(A1234/24 NOTAMR A1200/24 Q) ZSHA/QMRLC/IV/NBO/A/000/999/3112N12130E005 A) ZSPD \
+             B) 2402010000 C) PERM E) RWY 17L/35R CLSD <WIP> & MARKED)",
+        );
+        let id = Uuid::new_v5(&Uuid::NAMESPACE_URL, b"urn:x-notam:A1234/24:ZSHA");
+        assert!(xml.contains(&format!("<event:Event gml:id=\"uuid.{}\">", id)));
+        assert!(xml.contains(&format!("<gml:identifier codeSpace=\"urn:uuid:\">{}</gml:identifier>", id)));
+        assert!(xml.contains("<event:number>1234</event:number>"));
+        assert!(!xml.contains("A1234/24"));
+        assert!(xml.contains("<event:type>R</event:type>"));
+        assert!(xml.contains("<event:referredNumber>1200</event:referredNumber>"));
+        assert!(xml.contains("<event:coordinates>3112N12130E</event:coordinates>"));
+        assert!(xml.contains("<gml:beginPosition>2024-02-01T00:00:00Z</gml:beginPosition>"));
+        assert!(xml.contains("<gml:endPosition indeterminatePosition=\"unknown\"/>"));
+        assert!(xml.contains("<event:text>RWY 17L/35R CLSD &lt;WIP&gt; &amp; MARKED</event:text>"));
+        assert!(!xml.contains("aixm:Airspace"));
+    }
+
+    #[test]
+    fn writes_airspace_for_text_shapes_and_warning_areas() {
+        let xml = export(
+            "(A0200/24 NOTAMN Q) ZBPE/QRTCA/IV/BO/W/000/120/4004N11635E010 A) ZBPE B) 2402010000 \
+             C) 2402010600 E) TEMPO RESTRICTED AREA 4000N11630E-4000N11640E-4010N11640E-4000N11630E \
+             F) SFC G) FL120)",
+        );
+        assert!(xml.contains("<aixm:interpretation>TEMPDELTA</aixm:interpretation>"));
+        assert!(xml.contains("<gml:posList>40 116.5 40 116.666667 40.166667 116.666667 40 116.5</gml:posList>"));
+        assert!(xml.contains("<aixm:upperLimit uom=\"FL\">120</aixm:upperLimit>"));
+        assert!(xml.contains("<aixm:lowerLimit uom=\"OTHER\">GND</aixm:lowerLimit>"));
+        let event = Uuid::new_v5(&Uuid::NAMESPACE_URL, b"urn:x-notam:A0200/24:ZBPE");
+        assert!(xml.contains(&format!("<event:theEvent xlink:href=\"urn:uuid:{}\"/>", event)));
+        let airspace = Uuid::new_v5(&event, b"aixm:Airspace");
+        assert!(xml.contains(&format!("<aixm:Airspace gml:id=\"uuid.{}\">", airspace)));
+
+        let xml = export(
+            "(A0201/24 NOTAMN Q) ZBPE/QWELW/IV/BO/W/000/120/4004N11635E010 A) ZBPE B) 2402010000 \
+             C) 2402010600 E) ROCKET LAUNCH)",
+        );
+        assert!(xml.contains("<gml:CircleByCenterPoint numArc=\"1\">"));
+        assert!(xml.contains("<gml:radius uom=\"[nmi_i]\">10</gml:radius>"));
+        assert!(xml.contains("<aixm:upperLimit uom=\"FL\">120</aixm:upperLimit>"));
+    }
+}
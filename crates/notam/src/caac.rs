@@ -0,0 +1,123 @@
+//! CAAC domestic NOTAM profile.
+//!
+//! Domestic NOTAMs issued in China keep the ICAO fields but are often written with
+//! full-width punctuation (`（`, `Ｅ）`), preceded by a Chinese heading such as
+//! `国内航行通告`, and carry Chinese E) text. The heading is skipped when reading the
+//! header, and the Chinese terms of E) are mapped to ICAO abbreviations so the
+//! records are extracted as for an English E).
+
+/// Chinese E) terms and their ICAO abbreviations, longer terms first.
+const TERMS: &[(&str, &str)] = &[
+    ("仪表着陆系统", "ILS"),
+    ("航向信标台", "LOC"),
+    ("下滑信标台", "GP"),
+    ("全向信标台", "VOR"),
+    ("无方向信标台", "NDB"),
+    ("精密进近航道指示器", "PAPI"),
+    ("进近灯光系统", "ALS"),
+    ("跑道边灯", "REDL"),
+    ("跑道中线灯", "RCLL"),
+    ("跑道入口灯", "RTHL"),
+    ("接地带灯", "TDZL"),
+    ("机场灯标", "ABN"),
+    ("航向台", "LOC"),
+    ("下滑台", "GP"),
+    ("测距仪", "DME"),
+    ("进近灯", "ALS"),
+    ("滑行道", "TWY"),
+    ("跑道", "RWY"),
+    ("障碍物", "OBST"),
+    ("塔吊", "CRANE"),
+    ("起重机", "CRANE"),
+    ("铁塔", "TOWER"),
+    ("不工作", "U/S"),
+    ("不可用", "U/S"),
+    ("停用", "U/S"),
+    ("关闭", "CLSD"),
+];
+
+/// Text from the first ASCII letter or digit on, dropping a Chinese heading before the
+/// NOTAM header.
+pub(crate) fn skip_heading(text: &str) -> &str {
+    text.find(|ch: char| ch.is_ascii_alphanumeric())
+        .map_or(text, |idx| &text[idx..])
+}
+
+/// E) text with Chinese terms replaced by ICAO abbreviations and separated by spaces,
+/// e.g. `跑道01/19关闭。` becomes `RWY 01/19 CLSD 。`. Other text is kept.
+pub fn caac_body_terms(text: &str) -> String {
+    let folded = crate::fold_width(text);
+    let mut result = String::new();
+    let mut rest = folded.as_str();
+    while let Some(ch) = rest.chars().next() {
+        match TERMS.iter().find(|(term, _)| rest.starts_with(term)) {
+            Some((term, abbreviation)) => {
+                result.push(' ');
+                result.push_str(abbreviation);
+                result.push(' ');
+                rest = &rest[term.len()..];
+            }
+            None => {
+                if matches!(ch, '。' | '、') {
+                    result.push(' ');
+                }
+                result.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use aviation_wx_core::{Dialect, NavaidKind, NotamRecord};
+
+    use super::*;
+    use crate::{normalize_notam, parse_notam, parse_notam_with_dialect};
+
+    #[test]
+    fn maps_chinese_terms_to_abbreviations() {
+        assert_eq!(caac_body_terms("跑道01/19关闭。"), "RWY 01/19 CLSD 。");
+        assert_eq!(caac_body_terms("ＩＬＳ航向台不工作"), "ILS LOC U/S");
+        assert_eq!(skip_heading("国内航行通告\nC0123/24 NOTAMN"), "C0123/24 NOTAMN");
+    }
+
+    #[test]
+    fn parses_full_width_domestic_notam() {
+        let raw = "国内航行通告\n（C0123/24 NOTAMN\nＱ）ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005\n\
+                   Ａ）ZBAA Ｂ）2402010000 Ｃ）2402100000\nＥ）跑道01/19关闭，道面维护。）";
+        let (parsed, warnings) = parse_notam(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.dialect, Dialect::Caac);
+        assert_eq!(parsed.header.as_ref().map(|header| header.id.to_string()).as_deref(), Some("C0123/24"));
+        assert_eq!(parsed.a.as_deref(), Some("ZBAA"));
+        assert_eq!(parsed.e.as_deref(), Some("跑道01/19关闭，道面维护。"));
+        assert!(parsed.period.is_some());
+
+        let normalized = normalize_notam(&parsed);
+        assert!(matches!(
+            normalized.records.as_slice(),
+            [NotamRecord::RunwayClosure { runway, .. }] if runway == "01/19"
+        ));
+    }
+
+    #[test]
+    fn applies_profile_only_to_caac_notams() {
+        let raw = "A0100/24 NOTAMN A) EGLL B) 2402010000 C) 2402100000 E) 仪表着陆系统不工作";
+        let (parsed, _) = parse_notam(raw);
+        assert_eq!(parsed.dialect, Dialect::Icao);
+        assert!(normalize_notam(&parsed).records.is_empty());
+
+        for other in ["KJFK", "CYYZ", "YSSY"] {
+            let (parsed, _) = parse_notam(&raw.replace("EGLL", other));
+            assert_eq!(parsed.dialect, Dialect::Icao, "{}", other);
+        }
+
+        let (parsed, _) = parse_notam_with_dialect(raw, Some(Dialect::Caac));
+        assert!(matches!(
+            normalize_notam(&parsed).records.as_slice(),
+            [NotamRecord::NavaidOutage { kind: NavaidKind::Ils, .. }]
+        ));
+    }
+}
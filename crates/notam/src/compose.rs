@@ -0,0 +1,339 @@
+//! Composition of ICAO NOTAMs from structured input.
+//!
+//! `compose_notam` formats a `NotamDraft` as the NOTAM text an AIS office would
+//! transmit, then decodes that text again and applies the checks of `decode_notam`
+//! (header, Q) line and period), so a draft is held to the same rules as a received
+//! NOTAM. A `NOTAMC` is written without C), D), F) and G); other drafts need an end
+//! time or `permanent` for C).
+
+use aviation_wx_core::{
+    issue, issue_from_legacy, Issue, NotamDateTime, NotamId, NotamKind, NotamPeriod, QPurpose, QScope, QTraffic,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::header::header_issues;
+use crate::parse_notam;
+use crate::period::period_issues;
+use crate::validate::q_line_issues;
+
+/// Structured NOTAM input. Text fields are written as given, in upper case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotamDraft {
+    pub id: NotamId,
+    pub kind: NotamKind,
+    /// NOTAM replaced or cancelled; required for `NOTAMR` and `NOTAMC`.
+    pub references: Option<NotamId>,
+    pub fir: String,
+    /// Five-letter Q-code, e.g. `QMRLC`.
+    pub q_code: String,
+    pub traffic: Vec<QTraffic>,
+    pub purpose: Vec<QPurpose>,
+    pub scope: Vec<QScope>,
+    pub lower_fl: u16,
+    pub upper_fl: u16,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub radius_nm: Option<u16>,
+    /// A) location indicators.
+    pub locations: Vec<String>,
+    pub period: Option<NotamPeriod>,
+    /// D) schedule.
+    pub schedule: Option<String>,
+    /// E) text.
+    pub text: String,
+    /// F) lower limit, e.g. `SFC`.
+    pub lower_limit: Option<String>,
+    /// G) upper limit, e.g. `FL120`.
+    pub upper_limit: Option<String>,
+}
+
+impl NotamDraft {
+    /// Draft with the usual aerodrome qualifiers `IV/NBO/A/000/999` and no content.
+    pub fn new(id: NotamId, kind: NotamKind) -> Self {
+        Self {
+            id,
+            kind,
+            references: None,
+            fir: String::new(),
+            q_code: String::new(),
+            traffic: vec![QTraffic::Ifr, QTraffic::Vfr],
+            purpose: vec![QPurpose::ImmediateAttention, QPurpose::Briefing, QPurpose::OperationallySignificant],
+            scope: vec![QScope::Aerodrome],
+            lower_fl: 0,
+            upper_fl: 999,
+            latitude: None,
+            longitude: None,
+            radius_nm: None,
+            locations: Vec::new(),
+            period: None,
+            schedule: None,
+            text: String::new(),
+            lower_limit: None,
+            upper_limit: None,
+        }
+    }
+}
+
+/// Formatted NOTAM with the issues found when decoding it again.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotamComposition {
+    pub text: String,
+    pub warnings: Vec<Issue>,
+    pub errors: Vec<Issue>,
+}
+
+impl NotamComposition {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Formats `draft` as an ICAO NOTAM and validates the result.
+pub fn compose_notam(draft: &NotamDraft) -> NotamComposition {
+    let text = format_notam(draft);
+    let (parsed, warnings_legacy) = parse_notam(&text);
+
+    let mut warnings: Vec<Issue> = warnings_legacy.iter().map(|item| issue_from_legacy(item)).collect();
+    warnings.extend(header_issues(&parsed));
+    warnings.extend(q_line_issues(&parsed));
+    let mut errors = period_issues(&parsed);
+    let cancel = draft.kind == NotamKind::Cancel;
+    for (tag, missing) in [
+        ("Q)", draft.fir.trim().is_empty() || draft.q_code.trim().is_empty()),
+        ("A)", draft.locations.iter().all(|location| location.trim().is_empty())),
+        ("B)", draft.period.is_none()),
+        (
+            "C)",
+            !cancel && draft.period.as_ref().is_some_and(|period| period.end.is_none() && !period.permanent),
+        ),
+        ("E)", draft.text.trim().is_empty()),
+    ] {
+        if missing {
+            errors.push(issue(
+                "notam_field_missing",
+                format!("NOTAM draft has no {} field.", tag),
+                Some(tag.to_string()),
+            ));
+        }
+    }
+
+    NotamComposition { text, warnings, errors }
+}
+
+fn format_notam(draft: &NotamDraft) -> String {
+    let kind = match draft.kind {
+        NotamKind::New => 'N',
+        NotamKind::Replace => 'R',
+        NotamKind::Cancel => 'C',
+    };
+    let mut header = format!("({} NOTAM{}", draft.id, kind);
+    if let Some(reference) = &draft.references {
+        header.push_str(&format!(" {}", reference));
+    }
+    let cancel = draft.kind == NotamKind::Cancel;
+
+    let mut lines = vec![header, format!("Q) {}", format_q_line(draft))];
+    let mut fields = vec![format!("A) {}", upper_words(&draft.locations.join(" ")))];
+    if let Some(period) = &draft.period {
+        fields.push(format!("B) {}", format_datetime(&period.start)));
+        let end = match (&period.end, period.permanent) {
+            _ if cancel => None,
+            (_, true) => Some("PERM".to_string()),
+            (Some(end), false) if period.estimated => Some(format!("{} EST", format_datetime(end))),
+            (Some(end), false) => Some(format_datetime(end)),
+            (None, false) => None,
+        };
+        if let Some(end) = end {
+            fields.push(format!("C) {}", end));
+        }
+    }
+    lines.push(fields.join(" "));
+    if let Some(schedule) = draft.schedule.as_deref().filter(|_| !cancel) {
+        lines.push(format!("D) {}", upper_words(schedule)));
+    }
+    lines.push(format!("E) {}", draft.text.trim().to_uppercase()));
+    let limits: Vec<String> = [("F)", &draft.lower_limit), ("G)", &draft.upper_limit)]
+        .into_iter()
+        .filter(|_| !cancel)
+        .filter_map(|(tag, value)| value.as_deref().map(|value| format!("{} {}", tag, upper_words(value))))
+        .collect();
+    if !limits.is_empty() {
+        lines.push(limits.join(" "));
+    }
+
+    let mut text = lines.join("\n");
+    text.push(')');
+    text
+}
+
+fn format_q_line(draft: &NotamDraft) -> String {
+    let traffic = draft
+        .traffic
+        .iter()
+        .map(|item| match item {
+            QTraffic::Ifr => 'I',
+            QTraffic::Vfr => 'V',
+            QTraffic::Checklist => 'K',
+        })
+        .collect::<String>();
+    let purpose = draft
+        .purpose
+        .iter()
+        .map(|item| match item {
+            QPurpose::ImmediateAttention => 'N',
+            QPurpose::Briefing => 'B',
+            QPurpose::OperationallySignificant => 'O',
+            QPurpose::Miscellaneous => 'M',
+            QPurpose::Checklist => 'K',
+        })
+        .collect::<String>();
+    let scope = draft
+        .scope
+        .iter()
+        .map(|item| match item {
+            QScope::Aerodrome => 'A',
+            QScope::EnRoute => 'E',
+            QScope::NavigationWarning => 'W',
+            QScope::Checklist => 'K',
+        })
+        .collect::<String>();
+    let mut items = vec![
+        draft.fir.trim().to_uppercase(),
+        draft.q_code.trim().to_uppercase(),
+        traffic,
+        purpose,
+        scope,
+        format!("{:03}", draft.lower_fl),
+        format!("{:03}", draft.upper_fl),
+    ];
+    if let (Some(lat), Some(lon)) = (draft.latitude, draft.longitude) {
+        let mut coordinates = format!(
+            "{}{}",
+            format_angle(lat, 2, ['N', 'S']),
+            format_angle(lon, 3, ['E', 'W'])
+        );
+        if let Some(radius) = draft.radius_nm {
+            coordinates.push_str(&format!("{:03}", radius));
+        }
+        items.push(coordinates);
+    }
+    items.join("/")
+}
+
+/// Degrees and whole minutes, e.g. `3112N` or `12130E`.
+fn format_angle(value: f64, degree_digits: usize, hemispheres: [char; 2]) -> String {
+    let minutes = (value.abs() * 60.0).round() as u32;
+    let hemisphere = if value < 0.0 { hemispheres[1] } else { hemispheres[0] };
+    format!(
+        "{:0width$}{:02}{}",
+        minutes / 60,
+        minutes % 60,
+        hemisphere,
+        width = degree_digits
+    )
+}
+
+fn format_datetime(time: &NotamDateTime) -> String {
+    format!(
+        "{:02}{:02}{:02}{:02}{:02}",
+        time.year % 100,
+        time.month,
+        time.day,
+        time.hour,
+        time.minute
+    )
+}
+
+fn upper_words(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_notam_id;
+
+    fn runway_closure() -> NotamDraft {
+        let mut draft = NotamDraft::new(parse_notam_id("A1234/24").unwrap(), NotamKind::New);
+        draft.fir = "ZSHA".to_string();
+        draft.q_code = "QMRLC".to_string();
+        draft.latitude = Some(31.2);
+        draft.longitude = Some(121.5);
+        draft.radius_nm = Some(5);
+        draft.locations = vec!["zspd".to_string()];
+        draft.period = Some(NotamPeriod {
+            start: NotamDateTime::new(2024, 2, 1, 0, 0).unwrap(),
+            end: NotamDateTime::new(2024, 2, 10, 23, 59),
+            estimated: true,
+            permanent: false,
+        });
+        draft.schedule = Some("DAILY 0000-0600".to_string());
+        draft.text = "RWY 17L/35R CLSD".to_string();
+        draft
+    }
+
+    #[test]
+    fn formats_and_round_trips_a_draft() {
+        let composition = compose_notam(&runway_closure());
+        assert_eq!(
+            composition.text,
+            "(A1234/24 NOTAMN\nQ) ZSHA/QMRLC/IV/NBO/A/000/999/3112N12130E005\n\
+             A) ZSPD B) 2402010000 C) 2402102359 EST\nD) DAILY 0000-0600\nE) RWY 17L/35R CLSD)"
+        );
+        assert!(composition.is_valid());
+        assert!(composition.warnings.is_empty(), "{:?}", composition.warnings);
+
+        let (parsed, _) = parse_notam(&composition.text);
+        assert_eq!(parsed.period, runway_closure().period);
+        assert_eq!(parsed.e.as_deref(), Some("RWY 17L/35R CLSD"));
+    }
+
+    #[test]
+    fn applies_decoding_rules_to_drafts() {
+        let mut draft = runway_closure();
+        draft.kind = NotamKind::Replace;
+        draft.locations.push("RJTT".to_string());
+        draft.text.clear();
+        if let Some(period) = draft.period.as_mut() {
+            period.end = NotamDateTime::new(2024, 1, 1, 0, 0);
+        }
+        let composition = compose_notam(&draft);
+        let codes = |issues: &[Issue]| issues.iter().map(|item| item.code.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            codes(&composition.warnings),
+            vec!["notam_reference_missing", "notam_fir_mismatch", "notam_scope_mismatch"]
+        );
+        assert_eq!(codes(&composition.errors), vec!["notam_period_invalid", "notam_field_missing"]);
+        assert!(!composition.is_valid());
+
+        let mut cancel = runway_closure();
+        cancel.kind = NotamKind::Cancel;
+        cancel.references = parse_notam_id("A1200/24");
+        cancel.lower_limit = Some("SFC".to_string());
+        let composition = compose_notam(&cancel);
+        assert!(!composition.text.contains("C)") && !composition.text.contains("F)"));
+        assert!(composition.warnings.is_empty(), "{:?}", composition.warnings);
+    }
+
+    #[test]
+    fn writes_perm_only_for_permanent_drafts() {
+        let mut draft = runway_closure();
+        if let Some(period) = draft.period.as_mut() {
+            period.end = None;
+            period.estimated = false;
+        }
+        let composition = compose_notam(&draft);
+        assert!(!composition.text.contains("C)"), "{}", composition.text);
+        assert!(composition
+            .errors
+            .iter()
+            .any(|item| item.code == "notam_field_missing" && item.token.as_deref() == Some("C)")));
+
+        if let Some(period) = draft.period.as_mut() {
+            period.permanent = true;
+        }
+        let composition = compose_notam(&draft);
+        assert!(composition.text.contains("C) PERM"), "{}", composition.text);
+        assert!(composition.is_valid(), "{:?}", composition.errors);
+    }
+}
@@ -0,0 +1,147 @@
+//! FAA domestic NOTAMs: `!ACCT MM/NNN LOC KEYWORD body YYMMDDhhmm-YYMMDDhhmm[EST]`.
+//!
+//! The location is stored in A), the keyword and body in E), and the effective times in
+//! B) and C), so domestic NOTAMs share the period handling of ICAO NOTAMs.
+
+use aviation_wx_core::{Dialect, FaaNotamHeader, NotamParsed};
+
+use crate::period::parse_period;
+
+const KEYWORDS: &[&str] = &[
+    "RWY", "TWY", "APRON", "AD", "OBST", "NAV", "COM", "SVC", "AIRSPACE", "ODP", "SID", "STAR", "CHART", "DATA",
+    "IAP", "VFP", "ROUTE", "SPECIAL", "SECURITY", "FDC",
+];
+
+/// Parses a domestic NOTAM starting with `!`. `raw_lines` are kept as given.
+pub fn parse_faa_notam(text: &str, raw_lines: Vec<String>) -> (NotamParsed, Vec<String>) {
+    let mut warnings = Vec::new();
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let upper = |idx: usize| tokens.get(idx).map(|token| token.to_ascii_uppercase());
+
+    let accountability = upper(0)
+        .and_then(|token| token.strip_prefix('!').map(str::to_string))
+        .unwrap_or_default();
+    let number = tokens.get(1).copied().unwrap_or_default();
+    if !is_number(number) {
+        warnings.push(format!("Unrecognized token: {}", number));
+    }
+    let location = upper(2).unwrap_or_default();
+    let keyword = upper(3).filter(|token| KEYWORDS.contains(&token.as_str()));
+
+    let time_idx = tokens.iter().rposition(|token| split_times(token).is_some());
+    let (b, c) = match time_idx.and_then(|idx| split_times(tokens[idx])) {
+        Some((start, end)) => (Some(start), Some(end)),
+        None => {
+            warnings.push("FAA domestic NOTAM effective time not found.".to_string());
+            (None, None)
+        }
+    };
+    let body_end = time_idx.unwrap_or(tokens.len());
+    let e = tokens.get(3..body_end).map(|body| body.join(" ")).filter(|body| !body.is_empty());
+    if let Some(idx) = time_idx {
+        for token in &tokens[idx + 1..] {
+            warnings.push(format!("Unrecognized token: {}", token));
+        }
+    }
+
+    let (period, period_warnings) = parse_period(b.as_deref(), c.as_deref());
+    warnings.extend(period_warnings);
+    let faa_header = FaaNotamHeader {
+        accountability,
+        number: number.to_string(),
+        location: location.clone(),
+        keyword,
+        raw: tokens.get(..4.min(tokens.len())).unwrap_or_default().join(" "),
+    };
+
+    (
+        NotamParsed {
+            header: None,
+            faa_header: Some(Box::new(faa_header)),
+            dialect: Dialect::Faa,
+            q_line: None,
+            qualifiers: None,
+            period,
+            schedule: None,
+            lower_limit: None,
+            upper_limit: None,
+            a: (!location.is_empty()).then_some(location),
+            b,
+            c,
+            d: None,
+            e,
+            f: None,
+            g: None,
+            raw_lines,
+        },
+        warnings,
+    )
+}
+
+fn is_number(token: &str) -> bool {
+    match token.split_once('/') {
+        Some((left, right)) => {
+            (1..=2).contains(&left.len())
+                && (1..=4).contains(&right.len())
+                && left.chars().chain(right.chars()).all(|ch| ch.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// Splits `START-END` into B) and C) contents; the end may be `PERM` or carry `EST`.
+fn split_times(token: &str) -> Option<(String, String)> {
+    let upper = token.to_ascii_uppercase();
+    let (start, end) = upper.split_once('-')?;
+    let is_time = |text: &str| text.len() == 10 && text.chars().all(|ch| ch.is_ascii_digit());
+    if !is_time(start) {
+        return None;
+    }
+    let end = if end == "PERM" || is_time(end) {
+        end.to_string()
+    } else {
+        match end.strip_suffix("EST") {
+            Some(time) if is_time(time) => format!("{} EST", time),
+            _ => return None,
+        }
+    };
+    Some((start.to_string(), end))
+}
+
+#[cfg(test)]
+mod tests {
+    use aviation_wx_core::NotamDateTime;
+
+    use crate::parse_notam;
+
+    #[test]
+    fn parses_domestic_runway_closure() {
+        let (parsed, warnings) = parse_notam("!JFK 01/123 JFK RWY 4L/22R CLSD 2401011200-2401011800");
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let header = parsed.faa_header.expect("faa header");
+        assert_eq!(header.accountability, "JFK");
+        assert_eq!(header.number, "01/123");
+        assert_eq!(header.keyword.as_deref(), Some("RWY"));
+        assert_eq!(parsed.a.as_deref(), Some("JFK"));
+        assert_eq!(parsed.e.as_deref(), Some("RWY 4L/22R CLSD"));
+        let period = parsed.period.expect("period");
+        assert_eq!(period.start, NotamDateTime::new(2024, 1, 1, 12, 0).unwrap());
+        assert_eq!(period.end, NotamDateTime::new(2024, 1, 1, 18, 0));
+    }
+
+    #[test]
+    fn handles_est_perm_and_missing_times() {
+        let (parsed, _) = parse_notam("!ORD 02/045 ORD NAV ILS RWY 10L U/S 2402011200-2402031800EST");
+        let period = parsed.period.expect("period");
+        assert!(period.estimated);
+        assert_eq!(parsed.c.as_deref(), Some("2402031800 EST"));
+
+        let (parsed, _) = parse_notam("!FDC 4/1234 ZNY AIRSPACE TEMPORARY FLIGHT RESTRICTIONS 2401150000-PERM");
+        assert!(parsed.period.expect("period").permanent);
+        assert_eq!(parsed.faa_header.unwrap().accountability, "FDC");
+
+        let (parsed, warnings) = parse_notam("!BOS 03/007 BOS OBST TOWER LGT U/S");
+        assert!(parsed.period.is_none());
+        assert_eq!(warnings, vec!["FAA domestic NOTAM effective time not found.".to_string()]);
+    }
+}
@@ -0,0 +1,327 @@
+//! Geometry of a NOTAM: the Q) line centre and radius, and points, circles and
+//! polygons written in E), with conversion to GeoJSON.
+//!
+//! Coordinates in E) are `DDMM[SS][.s]N DDDMM[SS][.s]E`. Three or more coordinates
+//! joined by `-` form a polygon; a coordinate within a few words of `RADIUS n NM` (or
+//! `WI n NM OF`) is a circle centre; any other coordinate is a point. GeoJSON has no
+//! circle type, so circles are written as 64-sided polygons with a `radius_nm` property.
+
+use std::ops::Range;
+
+use aviation_wx_core::NotamNormalized;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+const EARTH_RADIUS_NM: f64 = 3440.065;
+const CIRCLE_SEGMENTS: usize = 64;
+/// Maximum distance in bytes between a radius and its centre coordinate in E).
+const CIRCLE_CENTRE_WINDOW: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GeometryShape {
+    Point { point: GeoPoint },
+    Circle { centre: GeoPoint, radius_nm: f64 },
+    Polygon { points: Vec<GeoPoint> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeometrySource {
+    QLine,
+    Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotamGeometry {
+    pub source: GeometrySource,
+    pub shape: GeometryShape,
+}
+
+/// Geometry of `notam`: the Q) line area first, then E) shapes in order of appearance.
+/// A Q) radius of 999 NM (whole FIR) is reported as a point.
+pub fn extract_geometry(notam: &NotamNormalized) -> Vec<NotamGeometry> {
+    let mut geometries = Vec::new();
+    if let Some(q) = &notam.qualifiers {
+        if let (Some(latitude), Some(longitude)) = (q.latitude, q.longitude) {
+            let centre = GeoPoint { latitude, longitude };
+            let shape = match q.radius_nm {
+                Some(radius) if radius < 999 => GeometryShape::Circle {
+                    centre,
+                    radius_nm: f64::from(radius),
+                },
+                _ => GeometryShape::Point { point: centre },
+            };
+            geometries.push(NotamGeometry {
+                source: GeometrySource::QLine,
+                shape,
+            });
+        }
+    }
+    if let Some(e) = &notam.e {
+        geometries.extend(text_geometry(e).into_iter().map(|shape| NotamGeometry {
+            source: GeometrySource::Text,
+            shape,
+        }));
+    }
+    geometries
+}
+
+/// Points, circles and polygons written in a free text such as E).
+pub fn text_geometry(text: &str) -> Vec<GeometryShape> {
+    let upper = text.to_ascii_uppercase();
+    let coordinates = find_coordinates(&upper);
+    let mut used = vec![false; coordinates.len()];
+    let mut shapes: Vec<(usize, GeometryShape)> = Vec::new();
+
+    let mut idx = 0;
+    while idx < coordinates.len() {
+        let mut end = idx;
+        while end + 1 < coordinates.len() && joined_by_dash(&upper, &coordinates[end].0, &coordinates[end + 1].0) {
+            end += 1;
+        }
+        let mut points: Vec<GeoPoint> = coordinates[idx..=end].iter().map(|(_, point)| *point).collect();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if points.len() >= 3 {
+            shapes.push((coordinates[idx].0.start, GeometryShape::Polygon { points }));
+            used[idx..=end].iter_mut().for_each(|flag| *flag = true);
+        }
+        idx = end + 1;
+    }
+
+    let radius_re = Regex::new(r"\b(?:RADIUS(?:\s+OF)?|WI)\s+(\d+(?:\.\d+)?)\s?(NM|KM)\b").unwrap();
+    for caps in radius_re.captures_iter(&upper) {
+        let whole = caps.get(0).unwrap().range();
+        let Ok(value) = caps[1].parse::<f64>() else {
+            continue;
+        };
+        let radius_nm = if &caps[2] == "KM" { value / 1.852 } else { value };
+        let after = (0..coordinates.len()).find(|&i| {
+            let start = coordinates[i].0.start;
+            !used[i] && start >= whole.end && start - whole.end <= CIRCLE_CENTRE_WINDOW
+        });
+        let before = (0..coordinates.len()).rev().find(|&i| {
+            let end = coordinates[i].0.end;
+            !used[i] && end <= whole.start && whole.start - end <= CIRCLE_CENTRE_WINDOW
+        });
+        if let Some(i) = after.or(before) {
+            used[i] = true;
+            shapes.push((
+                coordinates[i].0.start.min(whole.start),
+                GeometryShape::Circle {
+                    centre: coordinates[i].1,
+                    radius_nm,
+                },
+            ));
+        }
+    }
+
+    for (i, (range, point)) in coordinates.iter().enumerate() {
+        if !used[i] {
+            shapes.push((range.start, GeometryShape::Point { point: *point }));
+        }
+    }
+    shapes.sort_by_key(|(start, _)| *start);
+    shapes.into_iter().map(|(_, shape)| shape).collect()
+}
+
+/// Every coordinate in `text` (upper case) with its byte range.
+pub(crate) fn find_coordinates(text: &str) -> Vec<(Range<usize>, GeoPoint)> {
+    let re = Regex::new(r"\b(\d{4}(?:\d{2})?(?:\.\d+)?)([NS])\s?(\d{5}(?:\d{2})?(?:\.\d+)?)([EW])\b").unwrap();
+    re.captures_iter(text)
+        .filter_map(|caps| {
+            let latitude = degrees(&caps[1], 2)?;
+            let longitude = degrees(&caps[3], 3)?;
+            if latitude > 90.0 || longitude > 180.0 {
+                return None;
+            }
+            let point = GeoPoint {
+                latitude: if &caps[2] == "S" { -latitude } else { latitude },
+                longitude: if &caps[4] == "W" { -longitude } else { longitude },
+            };
+            Some((caps.get(0).unwrap().range(), point))
+        })
+        .collect()
+}
+
+/// `DD[D]MM[SS][.s]` as decimal degrees; the fraction belongs to the last field.
+/// `None` when the minutes or seconds are 60 or more.
+fn degrees(value: &str, degree_digits: usize) -> Option<f64> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let fraction: f64 = if fraction.is_empty() {
+        0.0
+    } else {
+        format!("0.{}", fraction).parse().ok()?
+    };
+    let deg: f64 = whole.get(..degree_digits)?.parse().ok()?;
+    let min: f64 = whole.get(degree_digits..degree_digits + 2)?.parse().ok()?;
+    if min >= 60.0 {
+        return None;
+    }
+    match whole.get(degree_digits + 2..) {
+        Some(sec) if !sec.is_empty() => {
+            let sec: f64 = sec.parse().ok()?;
+            (sec < 60.0).then(|| deg + min / 60.0 + (sec + fraction) / 3600.0)
+        }
+        _ => Some(deg + (min + fraction) / 60.0),
+    }
+}
+
+fn joined_by_dash(text: &str, first: &Range<usize>, second: &Range<usize>) -> bool {
+    text.get(first.end..second.start).is_some_and(|between| between.trim() == "-")
+}
+
+/// GeoJSON `FeatureCollection` of `geometries`, coordinates as `[longitude, latitude]`.
+/// Each feature has `source` and `shape` properties, and circles `radius_nm`.
+pub fn geometry_to_geojson(geometries: &[NotamGeometry]) -> Value {
+    let features: Vec<Value> = geometries
+        .iter()
+        .map(|geometry| {
+            let source = match geometry.source {
+                GeometrySource::QLine => "q_line",
+                GeometrySource::Text => "text",
+            };
+            let (shape, coordinates, radius) = match &geometry.shape {
+                GeometryShape::Point { point } => {
+                    ("point", json!({ "type": "Point", "coordinates": position(point) }), None)
+                }
+                GeometryShape::Circle { centre, radius_nm } => (
+                    "circle",
+                    json!({ "type": "Polygon", "coordinates": [ring(&circle_points(centre, *radius_nm))] }),
+                    Some(*radius_nm),
+                ),
+                GeometryShape::Polygon { points } => {
+                    ("polygon", json!({ "type": "Polygon", "coordinates": [ring(points)] }), None)
+                }
+            };
+            let mut properties = json!({ "source": source, "shape": shape });
+            if let Some(radius) = radius {
+                properties["radius_nm"] = json!(radius);
+            }
+            json!({ "type": "Feature", "geometry": coordinates, "properties": properties })
+        })
+        .collect();
+    json!({ "type": "FeatureCollection", "features": features })
+}
+
+fn position(point: &GeoPoint) -> Value {
+    json!([point.longitude, point.latitude])
+}
+
+/// Closed linear ring for GeoJSON.
+fn ring(points: &[GeoPoint]) -> Vec<Value> {
+    let mut ring: Vec<Value> = points.iter().map(position).collect();
+    if let Some(first) = points.first() {
+        ring.push(position(first));
+    }
+    ring
+}
+
+/// Points on the circle of `radius_nm` around `centre` on a spherical earth.
+fn circle_points(centre: &GeoPoint, radius_nm: f64) -> Vec<GeoPoint> {
+    let lat = centre.latitude.to_radians();
+    let lon = centre.longitude.to_radians();
+    let distance = radius_nm / EARTH_RADIUS_NM;
+    (0..CIRCLE_SEGMENTS)
+        .map(|step| {
+            let bearing = (step as f64) * std::f64::consts::TAU / CIRCLE_SEGMENTS as f64;
+            let point_lat = (lat.sin() * distance.cos() + lat.cos() * distance.sin() * bearing.cos()).asin();
+            let point_lon = lon
+                + (bearing.sin() * distance.sin() * lat.cos()).atan2(distance.cos() - lat.sin() * point_lat.sin());
+            GeoPoint {
+                latitude: point_lat.to_degrees(),
+                longitude: (point_lon.to_degrees() + 540.0) % 360.0 - 180.0,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_notam, parse_notam};
+
+    fn notam(raw: &str) -> NotamNormalized {
+        normalize_notam(&parse_notam(raw).0)
+    }
+
+    #[test]
+    fn extracts_q_line_circle_and_text_polygon() {
+        let geometries = extract_geometry(&notam(
+            "A0300/24 NOTAMN Q) ZSHA/QRTCA/IV/BO/W/000/060/3113N12132E010 A) ZSHA B) 2402010000 C) 2402020000 \
+             E) TEMPO RESTRICTED AREA ACT WI 3112N12130E-3115N12135E-3110N12138E-3112N12130E. F) SFC G) FL060",
+        ));
+        assert_eq!(geometries.len(), 2);
+        assert_eq!(geometries[0].source, GeometrySource::QLine);
+        assert!(matches!(geometries[0].shape, GeometryShape::Circle { radius_nm, .. } if radius_nm == 10.0));
+        let GeometryShape::Polygon { points } = &geometries[1].shape else {
+            panic!("expected polygon: {:?}", geometries[1]);
+        };
+        assert_eq!(points.len(), 3);
+        assert!((points[1].latitude - 31.25).abs() < 1e-9 && (points[1].longitude - 121.5833).abs() < 1e-4);
+    }
+
+    #[test]
+    fn finds_circles_and_points_in_text() {
+        let shapes = text_geometry(
+            "PJE WI CIRCLE RADIUS 2NM CENTRE 512830N0002745W. DROP ZONE 5130N00030W. \
+             AREA WI 10KM OF 5200N00100E",
+        );
+        assert_eq!(shapes.len(), 3);
+        assert!(matches!(&shapes[0], GeometryShape::Circle { centre, radius_nm } if *radius_nm == 2.0 && (centre.latitude - 51.475).abs() < 1e-9));
+        assert!(matches!(&shapes[1], GeometryShape::Point { point } if point.longitude == -0.5));
+        assert!(matches!(&shapes[2], GeometryShape::Circle { radius_nm, .. } if (radius_nm - 5.3996).abs() < 1e-3));
+    }
+
+    #[test]
+    fn rejects_minutes_and_seconds_out_of_range() {
+        assert!(find_coordinates("5175N00030W 5130N00061W 512860N0002745W 512830N0002760W").is_empty());
+        let found = find_coordinates("5159N00059W 515959N0005959W");
+        assert_eq!(found.len(), 2);
+        assert!((found[1].1.latitude - 51.99972).abs() < 1e-5);
+    }
+
+    #[test]
+    fn writes_geojson_feature_collection() {
+        let geometries = vec![
+            NotamGeometry {
+                source: GeometrySource::Text,
+                shape: GeometryShape::Polygon {
+                    points: vec![
+                        GeoPoint { latitude: 31.0, longitude: 121.0 },
+                        GeoPoint { latitude: 31.5, longitude: 121.0 },
+                        GeoPoint { latitude: 31.5, longitude: 121.5 },
+                    ],
+                },
+            },
+            NotamGeometry {
+                source: GeometrySource::QLine,
+                shape: GeometryShape::Circle {
+                    centre: GeoPoint { latitude: 40.0, longitude: 116.0 },
+                    radius_nm: 60.0,
+                },
+            },
+        ];
+        let geojson = geometry_to_geojson(&geometries);
+        assert_eq!(geojson["type"], "FeatureCollection");
+        let polygon = &geojson["features"][0]["geometry"]["coordinates"][0];
+        assert_eq!(polygon.as_array().unwrap().len(), 4);
+        assert_eq!(polygon[0], json!([121.0, 31.0]));
+        assert_eq!(polygon[0], polygon[3]);
+
+        let circle = &geojson["features"][1];
+        assert_eq!(circle["properties"], json!({ "source": "q_line", "shape": "circle", "radius_nm": 60.0 }));
+        let north = &circle["geometry"]["coordinates"][0][0];
+        assert!((north[1].as_f64().unwrap() - 41.0).abs() < 1e-3);
+        assert!((north[0].as_f64().unwrap() - 116.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,116 @@
+//! NOTAM header: `(A1234/24 NOTAMR A1200/24`.
+
+use aviation_wx_core::{issue, Issue, NotamHeader, NotamId, NotamKind, NotamParsed};
+use regex::Regex;
+
+/// Parses the header from the text before the first field tag. Leading `(` is ignored.
+pub fn parse_header(text: &str) -> Option<NotamHeader> {
+    let re = Regex::new(
+        r"^\(?\s*(?P<id>[A-Z]\d{4}/\d{2})\s*NOTAM(?P<kind>[NRC])(?:\s+(?P<reference>[A-Z]\d{4}/\d{2}))?",
+    )
+    .unwrap();
+    let trimmed = text.trim_start_matches('\u{feff}').trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let caps = re.captures(&upper)?;
+    let kind = match &caps["kind"] {
+        "N" => NotamKind::New,
+        "R" => NotamKind::Replace,
+        _ => NotamKind::Cancel,
+    };
+    Some(NotamHeader {
+        id: parse_notam_id(&caps["id"])?,
+        kind,
+        references: caps.name("reference").and_then(|m| parse_notam_id(m.as_str())),
+        raw: caps[0].trim_start_matches('(').trim().to_string(),
+    })
+}
+
+/// Parses an identifier such as `A1234/24`.
+pub fn parse_notam_id(value: &str) -> Option<NotamId> {
+    let (series_number, year) = value.split_once('/')?;
+    let series = series_number.get(..1)?;
+    if !series.chars().all(|ch| ch.is_ascii_uppercase()) || year.len() != 2 {
+        return None;
+    }
+    Some(NotamId {
+        series: series.to_string(),
+        number: series_number.get(1..)?.parse().ok()?,
+        year: year.parse().ok()?,
+    })
+}
+
+/// Checks the header against the NOTAM type: `NOTAMR`/`NOTAMC` need a referenced
+/// NOTAM, and a `NOTAMC` carries no C), D), F) or G).
+pub fn header_issues(parsed: &NotamParsed) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let Some(header) = &parsed.header else {
+        if !parsed.raw_lines.is_empty() && parsed.q_line.is_some() {
+            issues.push(issue("notam_header_missing", "NOTAM header (series/number and type) not found.", None));
+        }
+        return issues;
+    };
+    match header.kind {
+        NotamKind::New => {
+            if let Some(reference) = &header.references {
+                issues.push(issue(
+                    "notam_reference_unexpected",
+                    format!("NOTAMN {} should not reference {}.", header.id, reference),
+                    Some(reference.to_string()),
+                ));
+            }
+        }
+        NotamKind::Replace | NotamKind::Cancel => {
+            if header.references.is_none() {
+                issues.push(issue(
+                    "notam_reference_missing",
+                    format!("{} does not name the NOTAM it replaces or cancels.", header.raw),
+                    Some(header.raw.clone()),
+                ));
+            }
+        }
+    }
+    if header.kind == NotamKind::Cancel {
+        for (tag, value) in [("C)", &parsed.c), ("D)", &parsed.d), ("F)", &parsed.f), ("G)", &parsed.g)] {
+            if value.is_some() {
+                issues.push(issue(
+                    "notamc_unexpected_field",
+                    format!("NOTAMC should not contain a {} field.", tag),
+                    Some(tag.to_string()),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_notam;
+
+    #[test]
+    fn parses_replacement_header_in_parentheses() {
+        let (parsed, warnings) = parse_notam(
+            "(A1234/24 NOTAMR A1200/24\nQ) ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005\nA) ZBAA B) 2402010000 C) 2402012359\nE) RWY 18L/36R CLSD)",
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let header = parsed.header.expect("header");
+        assert_eq!(header.id.to_string(), "A1234/24");
+        assert_eq!(header.kind, NotamKind::Replace);
+        assert_eq!(header.references.map(|id| id.number), Some(1200));
+        assert_eq!(parsed.e.as_deref(), Some("RWY 18L/36R CLSD"));
+        assert!(parsed.period.is_some());
+    }
+
+    #[test]
+    fn notamc_with_extra_fields_is_flagged() {
+        let (parsed, _) = parse_notam(
+            "A1300/24 NOTAMC Q) ZBPE/QMRXX/IV/NBO/A/000/999/4004N11635E005 A) ZBAA B) 2402050000 C) 2402060000 E) CNL",
+        );
+        let issues = header_issues(&parsed);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].code, "notam_reference_missing");
+        assert_eq!(issues[1].code, "notamc_unexpected_field");
+        assert_eq!(issues[1].token.as_deref(), Some("C)"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Lexicons embedded from `crates/notam/lexicons`, the only copy; the web client
+//! imports the same files.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LexiconEntry {
+    pub en: String,
+    #[serde(rename = "zh-CN")]
+    pub zh_cn: String,
+}
+
+impl LexiconEntry {
+    /// Text in `lang`, falling back to English for languages without a translation.
+    pub fn text(&self, lang: &str) -> &str {
+        if lang == "zh-CN" {
+            &self.zh_cn
+        } else {
+            &self.en
+        }
+    }
+}
+
+type Lexicon = HashMap<String, LexiconEntry>;
+
+const Q_SUBJECT_JSON: &str = include_str!("../lexicons/notam_q_subject.json");
+const Q_CONDITION_JSON: &str = include_str!("../lexicons/notam_q_condition.json");
+const FIR_JSON: &str = include_str!("../lexicons/fir_codes.json");
+const BODY_JSON: &str = include_str!("../lexicons/notam_body.json");
+
+fn load(cell: &'static OnceLock<Lexicon>, json: &str) -> &'static Lexicon {
+    cell.get_or_init(|| serde_json::from_str(json).expect("embedded lexicon is valid JSON"))
+}
+
+/// Meaning of the second and third letters of a Q-code (`MR` = runway).
+pub fn q_subject(code: &str) -> Option<&'static LexiconEntry> {
+    static CELL: OnceLock<Lexicon> = OnceLock::new();
+    load(&CELL, Q_SUBJECT_JSON).get(code)
+}
+
+/// Meaning of the fourth and fifth letters of a Q-code (`LC` = closed).
+pub fn q_condition(code: &str) -> Option<&'static LexiconEntry> {
+    static CELL: OnceLock<Lexicon> = OnceLock::new();
+    load(&CELL, Q_CONDITION_JSON).get(code)
+}
+
+/// Name of a flight information region by ICAO location indicator.
+pub fn fir_name(code: &str) -> Option<&'static LexiconEntry> {
+    static CELL: OnceLock<Lexicon> = OnceLock::new();
+    load(&CELL, FIR_JSON).get(code)
+}
+
+/// Plain-language meaning of an E) abbreviation or phrase (`CLSD`, `U/S`, `DUE TO`).
+pub fn body_term(code: &str) -> Option<&'static LexiconEntry> {
+    static CELL: OnceLock<Lexicon> = OnceLock::new();
+    load(&CELL, BODY_JSON).get(code)
+}
+
+/// Expands the abbreviations and phrases (`WORK IN PROGRESS`, longest first) in an E)
+/// text into `lang` (`zh-CN`, otherwise English). Unknown tokens, runway designators and
+/// numbers are kept as written.
+pub fn expand_body(text: &str, lang: &str) -> String {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut expanded = Vec::new();
+    let mut idx = 0;
+    'tokens: while idx < tokens.len() {
+        for len in (2..=MAX_PHRASE_WORDS.min(tokens.len() - idx)).rev() {
+            let words = &tokens[idx..idx + len];
+            let (last, suffix) = split_suffix(words[len - 1]);
+            let phrase = words[..len - 1].iter().copied().chain([last]).collect::<Vec<_>>().join(" ");
+            if let Some(entry) = body_term(&phrase.to_ascii_uppercase()) {
+                expanded.push(format!("{}{}", entry.text(lang), suffix));
+                idx += len;
+                continue 'tokens;
+            }
+        }
+        expanded.push(expand_token(tokens[idx], lang));
+        idx += 1;
+    }
+    expanded.join(" ")
+}
+
+/// Words in the longest E) phrase of the body lexicon.
+const MAX_PHRASE_WORDS: usize = 3;
+
+fn term(code: &str, lang: &str) -> String {
+    body_term(code).map_or_else(|| code.to_string(), |entry| entry.text(lang).to_string())
+}
+
+fn expand_token(token: &str, lang: &str) -> String {
+    let upper = token.to_ascii_uppercase();
+    if let Some(pos) = upper.find("NR.") {
+        return format!("{}{}{}", &token[..pos], term("NR.", lang), &token[pos + 3..]);
+    }
+    let (core, suffix) = split_suffix(&upper);
+    match body_term(core) {
+        Some(entry) => format!("{}{}", entry.text(lang), suffix),
+        None => token.to_string(),
+    }
+}
+
+/// Splits trailing `.,;:` punctuation off a token.
+fn split_suffix(token: &str) -> (&str, &str) {
+    let core = token.trim_end_matches(['.', ',', ';', ':']);
+    (core, &token[core.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_q_codes() {
+        assert_eq!(q_subject("MR").map(|entry| entry.en.as_str()), Some("Runway (specify runway)"));
+        assert_eq!(q_condition("LC").map(|entry| entry.text("zh-CN")), Some("关闭"));
+        assert!(fir_name("ZBPE").is_some());
+        assert!(q_subject("??").is_none());
+    }
+
+    #[test]
+    fn expands_body_abbreviations() {
+        assert_eq!(expand_body("RWY 17L/35R CLSD DUE TO WIP.", "zh-CN"), "跑道 17L/35R 关闭 由于 施工中.");
+        assert_eq!(expand_body("ILS RWY 28R U/S", "en"), "ILS (instrument landing system) runway 28R unserviceable");
+        assert_eq!(expand_body("PARKING STAND NR.12 CLSD, work in progress", "en"), "parking stand nr.12 closed, work in progress");
+        assert_eq!(expand_body("TWY B CLSD WORK IN PROGRESS.", "zh-CN"), "滑行道 B 关闭 施工中.");
+    }
+
+    #[test]
+    fn keeps_function_words_as_written() {
+        assert_eq!(expand_body("CRANE IN RWY 01 APCH AREA", "zh-CN").split(' ').nth(1), Some("IN"));
+        assert_eq!(expand_body("AD CLSD FOR MAINT", "zh-CN").split(' ').nth(2), Some("FOR"));
+    }
+}
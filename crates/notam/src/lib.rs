@@ -1,9 +1,52 @@
 ﻿use aviation_wx_core::{ finalize_issues, issue_from_legacy,
-    DecodeResponse, DetailLevel, MessageType, NotamNormalized, NotamParsed,
+    DecodeResponse, DetailLevel, Dialect, MessageType, FaaNotamHeader, NotamExpandedText, NotamKind, NotamNormalized, NotamParsed, QLine,
 };
 use regex::Regex;
 
+pub mod compose;
+pub mod aixm;
+pub mod caac;
+pub mod faa;
+pub mod geometry;
+pub mod header;
+pub mod lexicon;
+pub mod period;
+pub mod qline;
+pub mod records;
+pub mod schedule;
+pub mod split;
+pub mod store;
+pub mod validate;
+pub mod vertical;
+
+pub use aixm::notam_to_aixm;
+pub use caac::caac_body_terms;
+pub use compose::{compose_notam, NotamComposition, NotamDraft};
+pub use faa::parse_faa_notam;
+pub use geometry::{
+    extract_geometry, geometry_to_geojson, text_geometry, GeoPoint, GeometryShape, GeometrySource, NotamGeometry,
+};
+pub use header::{header_issues, parse_header, parse_notam_id};
+pub use lexicon::{body_term, expand_body};
+pub use period::{parse_notam_datetime, parse_period, period_issues};
+pub use qline::parse_q_line;
+pub use records::extract_records;
+pub use schedule::{active_intervals, expand_schedule, is_active_at, parse_schedule};
+pub use split::{split_notams, NotamSegment};
+pub use store::NotamStore;
+pub use validate::q_line_issues;
+pub use vertical::{parse_vertical_limit, vertical_issues};
+
 pub fn parse_notam(raw: &str) -> (NotamParsed, Vec<String>) {
+    parse_notam_with_dialect(raw, None)
+}
+
+/// Parses a NOTAM using `dialect` or, when `None`, `Caac` for Chinese A) locations or
+/// Q) FIRs and `Icao` otherwise; `Faa` is only set by `parse_faa_notam`, since the FAA,
+/// Canadian and Australian station profiles cover METAR/TAF practice rather than ICAO
+/// NOTAMs. Full-width forms are read as their ASCII equivalents in the field tags and
+/// fields; E) is kept as written.
+pub fn parse_notam_with_dialect(raw: &str, dialect: Option<Dialect>) -> (NotamParsed, Vec<String>) {
     let mut warnings = Vec::new();
     let normalized_raw = raw.replace("\r\n", "\n").replace('\r', "\n");
     let raw_lines: Vec<String> = normalized_raw
@@ -12,13 +55,14 @@ pub fn parse_notam(raw: &str) -> (NotamParsed, Vec<String>) {
         .filter(|line| !line.is_empty())
         .collect();
 
-    let upper = normalized_raw.to_ascii_uppercase();
-    let tag_re = Regex::new(r"(Q\)|A\)|B\)|C\)|D\)|E\)|F\)|G\))").unwrap();
-    let mut positions: Vec<(usize, String)> = tag_re
-        .find_iter(&upper)
-        .map(|m| (m.start(), m.as_str().to_string()))
-        .collect();
-    positions.sort_by_key(|(pos, _)| *pos);
+    let body = strip_parentheses(&normalized_raw);
+    if body.starts_with('!') {
+        return parse_faa_notam(body, raw_lines);
+    }
+    let folded = fold_width(body);
+    let upper = folded.to_ascii_uppercase();
+    let positions = field_tags(&upper);
+    let header_end = positions.first().map_or(folded.len(), |(pos, _)| *pos);
 
     let mut q_line = None;
     let mut a = None;
@@ -41,11 +85,14 @@ pub fn parse_notam(raw: &str) -> (NotamParsed, Vec<String>) {
         } else {
             upper.len()
         };
-        let content = normalized_raw
-            .get(start + tag_len..end)
-            .unwrap_or("")
-            .trim()
-            .to_string();
+        let content = if tag == "E)" {
+            body.get(original_offset(body, &folded, start + tag_len)..original_offset(body, &folded, end))
+        } else {
+            folded.get(start + tag_len..end)
+        }
+        .unwrap_or("")
+        .trim()
+        .to_string();
         match tag.as_str() {
             "Q)" => q_line = if content.is_empty() { None } else { Some(content) },
             "A)" => a = if content.is_empty() { None } else { Some(content) },
@@ -59,9 +106,51 @@ pub fn parse_notam(raw: &str) -> (NotamParsed, Vec<String>) {
         }
     }
 
+    let qualifiers = match &q_line {
+        Some(raw) => {
+            let (line, q_warnings) = parse_q_line(raw);
+            warnings.extend(q_warnings);
+            line.map(Box::new)
+        }
+        None => None,
+    };
+    let dialect = dialect.unwrap_or_else(|| {
+        let location = a.as_deref().and_then(|a| a.split_whitespace().next());
+        match Dialect::from_station(location.or(qualifiers.as_ref().map(|q| q.fir.as_str())).unwrap_or_default()) {
+            Dialect::Caac => Dialect::Caac,
+            _ => Dialect::Icao,
+        }
+    });
+    let header_text = folded.get(..header_end).unwrap_or("");
+    let header = parse_header(if dialect == Dialect::Caac {
+        caac::skip_heading(header_text)
+    } else {
+        header_text
+    });
+    let (period, period_warnings) = parse_period(b.as_deref(), c.as_deref());
+    warnings.extend(period_warnings);
+    let schedule = match &d {
+        Some(raw) => {
+            let (schedule, schedule_warnings) = parse_schedule(raw);
+            warnings.extend(schedule_warnings);
+            schedule
+        }
+        None => None,
+    };
+    let lower_limit = f.as_deref().and_then(|raw| parse_limit_field(raw, &mut warnings));
+    let upper_limit = g.as_deref().and_then(|raw| parse_limit_field(raw, &mut warnings));
+
     (
         NotamParsed {
+            header,
+            faa_header: None,
+            dialect,
             q_line,
+            qualifiers,
+            period,
+            schedule,
+            lower_limit,
+            upper_limit,
             a,
             b,
             c,
@@ -75,14 +164,98 @@ pub fn parse_notam(raw: &str) -> (NotamParsed, Vec<String>) {
     )
 }
 
+fn parse_limit_field(raw: &str, warnings: &mut Vec<String>) -> Option<aviation_wx_core::VerticalLimit> {
+    let limit = parse_vertical_limit(raw);
+    if limit.is_none() {
+        warnings.push(format!("Unrecognized token: {}", raw));
+    }
+    limit
+}
+
+/// Drops the parentheses around an ICAO-formatted NOTAM, `(A1234/24 NOTAMN ... E) ...)`,
+/// together with a heading before `(` that has no ASCII letters or digits (`国内航行通告`).
+fn strip_parentheses(text: &str) -> &str {
+    let trimmed = text.trim().trim_start_matches('\u{feff}').trim_start();
+    let open = trimmed
+        .find(['(', '（'])
+        .filter(|idx| !trimmed[..*idx].chars().any(|ch| ch.is_ascii_alphanumeric()));
+    match open.and_then(|idx| trimmed[idx..].strip_prefix(['(', '（'])?.strip_suffix([')', '）'])) {
+        Some(inner) => inner,
+        None => trimmed,
+    }
+}
+
+/// Field tags `Q)` to `G)` of an upper-case NOTAM with their byte offsets. A tag follows
+/// whitespace or `(` and comes after the tags before it in `Q)`, `A)`, ..., `G)` order, so
+/// `(CAT B)` in E) is text rather than a B) field.
+pub(crate) fn field_tags(upper: &str) -> Vec<(usize, String)> {
+    let tag_re = Regex::new(r"(?:^|[\s(])(?P<tag>[QABCDEFG]\))").unwrap();
+    let mut last = None;
+    tag_re
+        .captures_iter(upper)
+        .filter_map(|caps| caps.name("tag"))
+        .filter_map(|m| {
+            let rank = "QABCDEFG".find(&m.as_str()[..1])?;
+            if last.is_some_and(|last| rank <= last) {
+                return None;
+            }
+            last = Some(rank);
+            Some((m.start(), m.as_str().to_string()))
+        })
+        .collect()
+}
+
+/// Replaces full-width forms (`Ｅ）`, `２４`) and the ideographic space with their ASCII
+/// equivalents. Every character maps to one character, so positions can be carried back
+/// to the input with `original_offset`.
+pub(crate) fn fold_width(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch {
+            '\u{ff01}'..='\u{ff5e}' => char::from_u32(ch as u32 - 0xfee0).unwrap_or(ch),
+            '\u{3000}' => ' ',
+            _ => ch,
+        })
+        .collect()
+}
+
+/// Byte offset in `text` of the character at byte `offset` of `fold_width(text)`.
+fn original_offset(text: &str, folded: &str, offset: usize) -> usize {
+    let chars = folded.get(..offset).map_or(0, |prefix| prefix.chars().count());
+    text.char_indices().nth(chars).map_or(text.len(), |(idx, _)| idx)
+}
+
 pub fn normalize_notam(parsed: &NotamParsed) -> NotamNormalized {
+    let body = match parsed.dialect {
+        Dialect::Caac => parsed.e.as_deref().map(caac_body_terms),
+        _ => parsed.e.clone(),
+    };
     NotamNormalized {
+        header: parsed.header.clone(),
+        faa_header: parsed.faa_header.clone(),
+        dialect: parsed.dialect,
         q_line: parsed.q_line.clone(),
+        qualifiers: parsed.qualifiers.clone(),
+        period: parsed.period.clone(),
+        schedule: parsed.schedule.clone(),
+        lower_limit: parsed.lower_limit.clone(),
+        upper_limit: parsed.upper_limit.clone(),
         a: parsed.a.clone(),
         b: parsed.b.clone(),
         c: parsed.c.clone(),
         d: parsed.d.clone(),
         e: parsed.e.clone(),
+        e_expanded: parsed.e.as_deref().map(|e| {
+            Box::new(NotamExpandedText {
+                en: expand_body(e, "en"),
+                zh_cn: expand_body(e, "zh-CN"),
+            })
+        }),
+        records: extract_records(
+            parsed.qualifiers.as_deref(),
+            parsed.faa_header.as_ref().and_then(|header| header.keyword.as_deref()),
+            body.as_deref(),
+            parsed.period.as_ref(),
+        ),
         f: parsed.f.clone(),
         g: parsed.g.clone(),
     }
@@ -94,30 +267,72 @@ pub fn translate_notam(normalized: &NotamNormalized, detail: DetailLevel, lang:
     }
 
     let mut parts = Vec::new();
+    if let Some(header) = &normalized.header {
+        parts.push(match (header.kind, &header.references) {
+            (NotamKind::Replace, Some(reference)) => format!("NOTAM {}（替代 {}）", header.id, reference),
+            (NotamKind::Cancel, Some(reference)) => format!("NOTAM {}（取消 {}）", header.id, reference),
+            (NotamKind::Replace, None) => format!("NOTAM {}（替代）", header.id),
+            (NotamKind::Cancel, None) => format!("NOTAM {}（取消）", header.id),
+            (NotamKind::New, _) => format!("NOTAM {}（新发）", header.id),
+        });
+    }
+    if let Some(header) = &normalized.faa_header {
+        parts.push(describe_faa_header(header));
+    }
     if let Some(a) = &normalized.a {
         parts.push(format!("地点 {}", a));
     }
-    if let Some(b) = &normalized.b {
-        parts.push(format!("生效 {}", b));
-    }
-    if let Some(c) = &normalized.c {
-        parts.push(format!("终止 {}", c));
+    match &normalized.period {
+        Some(period) => {
+            parts.push(format!("生效 {}", period.start));
+            match period.end {
+                Some(end) if period.estimated => parts.push(format!("终止 {}（预计）", end)),
+                Some(end) => parts.push(format!("终止 {}", end)),
+                None if period.permanent => parts.push("永久有效".to_string()),
+                None => {
+                    if let Some(c) = &normalized.c {
+                        parts.push(format!("终止 {}", c));
+                    }
+                }
+            }
+        }
+        None => {
+            if let Some(b) = &normalized.b {
+                parts.push(format!("生效 {}", b));
+            }
+            if let Some(c) = &normalized.c {
+                parts.push(format!("终止 {}", c));
+            }
+        }
     }
     if let Some(e) = &normalized.e {
         parts.push(format!("内容 {}", e));
+        if let Some(expanded) = normalized.e_expanded.as_ref().filter(|expanded| expanded.zh_cn != *e) {
+            parts.push(format!("释义 {}", expanded.zh_cn));
+        }
     }
     if detail == DetailLevel::Full {
-        if let Some(q) = &normalized.q_line {
+        if !normalized.records.is_empty() {
+            let records: Vec<String> = normalized.records.iter().map(records::describe_record).collect();
+            parts.push(format!("要点 {}", records.join("；")));
+        }
+        if let Some(q) = &normalized.qualifiers {
+            parts.push(describe_q_line(q));
+        } else if let Some(q) = &normalized.q_line {
             parts.push(format!("Q 行 {}", q));
         }
         if let Some(d) = &normalized.d {
             parts.push(format!("D {}", d));
         }
-        if let Some(f) = &normalized.f {
-            parts.push(format!("F {}", f));
+        match (&normalized.lower_limit, &normalized.f) {
+            (Some(limit), _) => parts.push(format!("下限 {}", vertical::describe_vertical_limit(limit))),
+            (None, Some(f)) => parts.push(format!("F {}", f)),
+            (None, None) => {}
         }
-        if let Some(g) = &normalized.g {
-            parts.push(format!("G {}", g));
+        match (&normalized.upper_limit, &normalized.g) {
+            (Some(limit), _) => parts.push(format!("上限 {}", vertical::describe_vertical_limit(limit))),
+            (None, Some(g)) => parts.push(format!("G {}", g)),
+            (None, None) => {}
         }
     }
 
@@ -128,14 +343,59 @@ pub fn translate_notam(normalized: &NotamNormalized, detail: DetailLevel, lang:
     }
 }
 
+fn describe_faa_header(header: &FaaNotamHeader) -> String {
+    let mut text = format!("美国国内 NOTAM !{} {}", header.accountability, header.number);
+    if let Some(entry) = header.keyword.as_deref().and_then(lexicon::body_term) {
+        text.push_str(&format!("（{}）", entry.zh_cn));
+    }
+    text
+}
+
+fn describe_q_line(q: &QLine) -> String {
+    let mut text = format!("Q 行 {}", q.fir);
+    if let Some(name) = lexicon::fir_name(&q.fir) {
+        text.push_str(&format!("（{}）", name.zh_cn));
+    }
+    let subject = lexicon::q_subject(&q.subject).map_or(q.subject.as_str(), |entry| entry.zh_cn.as_str());
+    let condition = lexicon::q_condition(&q.condition).map_or(q.condition.as_str(), |entry| entry.zh_cn.as_str());
+    text.push_str(&format!(" {}：{} / {}", q.code, subject, condition));
+    if let (Some(lower), Some(upper)) = (q.lower_fl, q.upper_fl) {
+        text.push_str(&format!("，FL{:03}-FL{:03}", lower, upper));
+    }
+    if let (Some(lat), Some(lon)) = (q.latitude, q.longitude) {
+        text.push_str(&format!(
+            "，中心 {:.4}{} {:.4}{}",
+            lat.abs(),
+            if lat < 0.0 { "S" } else { "N" },
+            lon.abs(),
+            if lon < 0.0 { "W" } else { "E" }
+        ));
+    }
+    if let Some(radius) = q.radius_nm {
+        text.push_str(&format!("，半径 {}NM", radius));
+    }
+    text
+}
+
 pub fn decode_notam(raw: &str, detail: DetailLevel, lang: &str) -> DecodeResponse {
-    let (parsed, warnings_legacy_raw) = parse_notam(raw);
+    decode_notam_with_dialect(raw, None, detail, lang)
+}
+
+pub fn decode_notam_with_dialect(
+    raw: &str,
+    dialect: Option<Dialect>,
+    detail: DetailLevel,
+    lang: &str,
+) -> DecodeResponse {
+    let (parsed, warnings_legacy_raw) = parse_notam_with_dialect(raw, dialect);
     let normalized = normalize_notam(&parsed);
     let explain = translate_notam(&normalized, detail, lang);
 
     let mut warnings: Vec<aviation_wx_core::Issue> =
         warnings_legacy_raw.iter().map(|item| issue_from_legacy(item)).collect();
-    let mut errors = Vec::new();
+    warnings.extend(header_issues(&parsed));
+    warnings.extend(q_line_issues(&parsed));
+    let mut errors = period_issues(&parsed);
     let (warnings_legacy, errors_legacy) = finalize_issues(&mut warnings, &mut errors);
 
     DecodeResponse {
@@ -0,0 +1,121 @@
+//! NOTAM B) and C) fields: `YYMMDDhhmm`, optionally followed by `EST` in C) (with or
+//! without a space), or `PERM`.
+
+use aviation_wx_core::{issue, Issue, NotamDateTime, NotamParsed, NotamPeriod};
+
+/// Parses a `YYMMDDhhmm` group. Two-digit years are taken as 20YY.
+pub fn parse_notam_datetime(token: &str) -> Option<NotamDateTime> {
+    if token.len() != 10 || !token.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| token[range].parse::<u8>().ok();
+    NotamDateTime::new(
+        2000 + field(0..2)? as u16,
+        field(2..4)?,
+        field(4..6)?,
+        field(6..8)?,
+        field(8..10)?,
+    )
+}
+
+/// Builds the effective period from the raw B) and C) contents. Returns `None` when
+/// B) is missing or unreadable; an unreadable C) leaves the end open.
+pub fn parse_period(b: Option<&str>, c: Option<&str>) -> (Option<NotamPeriod>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let start = b.and_then(|raw| {
+        let parsed = raw.split_whitespace().next().and_then(parse_notam_datetime);
+        if parsed.is_none() {
+            warnings.push(format!("Unrecognized token: {}", raw));
+        }
+        parsed
+    });
+
+    let mut end = None;
+    let mut estimated = false;
+    let mut permanent = false;
+    if let Some(raw) = c {
+        let tokens: Vec<String> = raw.split_whitespace().map(|token| token.to_ascii_uppercase()).collect();
+        match tokens.as_slice() {
+            [perm] if perm == "PERM" => permanent = true,
+            [time] => match time.strip_suffix("EST") {
+                Some(time) => {
+                    end = parse_notam_datetime(time);
+                    estimated = end.is_some();
+                }
+                None => end = parse_notam_datetime(time),
+            },
+            [time, est] if est == "EST" => {
+                end = parse_notam_datetime(time);
+                estimated = end.is_some();
+            }
+            _ => {}
+        }
+        if end.is_none() && !permanent {
+            warnings.push(format!("Unrecognized token: {}", raw));
+        }
+    }
+
+    let period = start.map(|start| NotamPeriod {
+        start,
+        end,
+        estimated,
+        permanent,
+    });
+    (period, warnings)
+}
+
+/// Errors for a C) that does not come after B).
+pub fn period_issues(parsed: &NotamParsed) -> Vec<Issue> {
+    let Some(period) = &parsed.period else {
+        return Vec::new();
+    };
+    match period.end {
+        Some(end) if end <= period.start => vec![issue(
+            "notam_period_invalid",
+            format!("C) {} is not after B) {}.", end, period.start),
+            parsed.c.clone(),
+        )],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_est_and_perm() {
+        let (period, warnings) = parse_period(Some("2401010000"), Some("2412312359 EST"));
+        assert!(warnings.is_empty());
+        let period = period.expect("period");
+        assert_eq!(period.start, NotamDateTime::new(2024, 1, 1, 0, 0).unwrap());
+        assert_eq!(period.end, NotamDateTime::new(2024, 12, 31, 23, 59));
+        assert!(period.estimated && !period.permanent);
+
+        let (attached, warnings) = parse_period(Some("2401010000"), Some("2412312359EST"));
+        assert!(warnings.is_empty());
+        assert_eq!(attached, Some(period));
+
+        let (period, _) = parse_period(Some("2402291200"), Some("PERM"));
+        let period = period.expect("period");
+        assert!(period.permanent && period.end.is_none());
+        assert!(period.contains(&NotamDateTime::new(2099, 1, 1, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert!(parse_notam_datetime("2302291200").is_none());
+        assert!(parse_notam_datetime("2401012400").is_none());
+        let (period, warnings) = parse_period(Some("WIE"), Some("2401010000"));
+        assert!(period.is_none());
+        assert_eq!(warnings, vec!["Unrecognized token: WIE".to_string()]);
+    }
+
+    #[test]
+    fn datetime_minutes_round_trip() {
+        let time = NotamDateTime::new(2024, 2, 29, 23, 59).unwrap();
+        assert_eq!(NotamDateTime::from_minutes(time.to_minutes()), time);
+        assert_eq!(NotamDateTime::from_minutes(time.to_minutes() + 1), NotamDateTime::new(2024, 3, 1, 0, 0).unwrap());
+        assert_eq!(time.weekday(), 3);
+    }
+}
@@ -0,0 +1,178 @@
+//! Decoding of the NOTAM Q) (qualifier) line:
+//! `FIR/QCODE/TRAFFIC/PURPOSE/SCOPE/LOWER/UPPER/COORDINATES`.
+
+use aviation_wx_core::{QLine, QPurpose, QScope, QTraffic};
+
+use crate::lexicon::{q_condition, q_subject};
+
+/// Parses a Q) line. Items that cannot be decoded are reported as unrecognized tokens
+/// and left empty; `None` is returned only when the FIR or Q-code is unusable.
+pub fn parse_q_line(raw: &str) -> (Option<QLine>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let items: Vec<String> = raw
+        .split('/')
+        .map(|item| item.split_whitespace().collect::<String>().to_ascii_uppercase())
+        .collect();
+
+    let fir = items.first().cloned().unwrap_or_default();
+    let code = items.get(1).cloned().unwrap_or_default();
+    let code_valid = code.len() == 5 && code.starts_with('Q') && code.chars().all(|ch| ch.is_ascii_alphabetic());
+    if fir.len() != 4 || !fir.chars().all(|ch| ch.is_ascii_alphanumeric()) || !code_valid {
+        warnings.push(format!("Unrecognized token: {}", raw.trim()));
+        return (None, warnings);
+    }
+    let subject = code[1..3].to_string();
+    let condition = code[3..5].to_string();
+
+    let item = |idx: usize| items.get(idx).map(String::as_str).filter(|value| !value.is_empty());
+    let traffic = decode_letters(item(2), &mut warnings, |ch| match ch {
+        'I' => Some(QTraffic::Ifr),
+        'V' => Some(QTraffic::Vfr),
+        'K' => Some(QTraffic::Checklist),
+        _ => None,
+    });
+    let purpose = decode_letters(item(3), &mut warnings, |ch| match ch {
+        'N' => Some(QPurpose::ImmediateAttention),
+        'B' => Some(QPurpose::Briefing),
+        'O' => Some(QPurpose::OperationallySignificant),
+        'M' => Some(QPurpose::Miscellaneous),
+        'K' => Some(QPurpose::Checklist),
+        _ => None,
+    });
+    let scope = decode_letters(item(4), &mut warnings, |ch| match ch {
+        'A' => Some(QScope::Aerodrome),
+        'E' => Some(QScope::EnRoute),
+        'W' => Some(QScope::NavigationWarning),
+        'K' => Some(QScope::Checklist),
+        _ => None,
+    });
+    let lower_fl = decode_level(item(5), &mut warnings);
+    let upper_fl = decode_level(item(6), &mut warnings);
+    let (latitude, longitude, radius_nm) = match item(7) {
+        Some(value) => match parse_coordinates(value) {
+            Some((lat, lon, radius)) => (Some(lat), Some(lon), radius),
+            None => {
+                warnings.push(format!("Unrecognized token: {}", value));
+                (None, None, None)
+            }
+        },
+        None => (None, None, None),
+    };
+
+    let line = QLine {
+        fir,
+        subject_text: q_subject(&subject).map(|entry| entry.en.clone()),
+        condition_text: q_condition(&condition).map(|entry| entry.en.clone()),
+        code,
+        subject,
+        condition,
+        traffic,
+        purpose,
+        scope,
+        lower_fl,
+        upper_fl,
+        latitude,
+        longitude,
+        radius_nm,
+        raw: raw.trim().to_string(),
+    };
+    (Some(line), warnings)
+}
+
+fn decode_letters<T>(value: Option<&str>, warnings: &mut Vec<String>, decode: impl Fn(char) -> Option<T>) -> Vec<T> {
+    let Some(value) = value else {
+        return Vec::new();
+    };
+    let decoded: Option<Vec<T>> = value.chars().map(decode).collect();
+    decoded.unwrap_or_else(|| {
+        warnings.push(format!("Unrecognized token: {}", value));
+        Vec::new()
+    })
+}
+
+fn decode_level(value: Option<&str>, warnings: &mut Vec<String>) -> Option<u16> {
+    let value = value?;
+    if value.len() == 3 && value.chars().all(|ch| ch.is_ascii_digit()) {
+        return value.parse().ok();
+    }
+    warnings.push(format!("Unrecognized token: {}", value));
+    None
+}
+
+/// `ddmmNdddmmE` with an optional three-digit radius in NM, as decimal degrees.
+fn parse_coordinates(value: &str) -> Option<(f64, f64, Option<u16>)> {
+    let bytes = value.as_bytes();
+    if bytes.len() != 11 && bytes.len() != 14 {
+        return None;
+    }
+    let digits = |range: std::ops::Range<usize>| -> Option<f64> {
+        let part = value.get(range)?;
+        part.chars().all(|ch| ch.is_ascii_digit()).then(|| part.parse().ok())?
+    };
+    let minutes = |range: std::ops::Range<usize>| digits(range).filter(|minutes| *minutes < 60.0);
+    let lat = digits(0..2)? + minutes(2..4)? / 60.0;
+    let lon = digits(5..8)? + minutes(8..10)? / 60.0;
+    let lat = match bytes[4] {
+        b'N' => lat,
+        b'S' => -lat,
+        _ => return None,
+    };
+    let lon = match bytes[10] {
+        b'E' => lon,
+        b'W' => -lon,
+        _ => return None,
+    };
+    if lat.abs() > 90.0 || lon.abs() > 180.0 {
+        return None;
+    }
+    let radius = if bytes.len() == 14 {
+        Some(digits(11..14)? as u16)
+    } else {
+        None
+    };
+    Some((lat, lon, radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_all_items() {
+        let (line, warnings) = parse_q_line("ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005");
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let line = line.expect("q line");
+        assert_eq!(line.fir, "ZBPE");
+        assert_eq!((line.subject.as_str(), line.condition.as_str()), ("MR", "LC"));
+        assert_eq!(line.subject_text.as_deref(), Some("Runway (specify runway)"));
+        assert_eq!(line.condition_text.as_deref(), Some("Closed"));
+        assert_eq!(line.traffic, vec![QTraffic::Ifr, QTraffic::Vfr]);
+        assert_eq!(
+            line.purpose,
+            vec![QPurpose::ImmediateAttention, QPurpose::Briefing, QPurpose::OperationallySignificant]
+        );
+        assert_eq!(line.scope, vec![QScope::Aerodrome]);
+        assert_eq!((line.lower_fl, line.upper_fl), (Some(0), Some(999)));
+        assert!((line.latitude.unwrap() - 40.0667).abs() < 1e-3);
+        assert!((line.longitude.unwrap() - 116.5833).abs() < 1e-3);
+        assert_eq!(line.radius_nm, Some(5));
+    }
+
+    #[test]
+    fn keeps_partial_lines_and_reports_bad_items() {
+        let (line, warnings) = parse_q_line("KZNY / QWELW / IV / BO / W / 000 / 1X0 / 3830S05820W");
+        let line = line.expect("q line");
+        assert_eq!(line.upper_fl, None);
+        assert!(line.latitude.unwrap() < 0.0 && line.longitude.unwrap() < 0.0);
+        assert_eq!(line.radius_nm, None);
+        assert_eq!(warnings, vec!["Unrecognized token: 1X0".to_string()]);
+
+        let (line, warnings) = parse_q_line("ZBPE/MRLC");
+        assert!(line.is_none());
+        assert_eq!(warnings.len(), 1);
+
+        let (line, warnings) = parse_q_line("ZBPE/QMRLC/IV/NBO/A/000/999/4075N11635E005");
+        assert_eq!(line.expect("q line").latitude, None);
+        assert_eq!(warnings, vec!["Unrecognized token: 4075N11635E005".to_string()]);
+    }
+}
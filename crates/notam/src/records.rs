@@ -0,0 +1,542 @@
+//! Extraction of the NOTAM items dispatch acts on: runway and taxiway closures, navaid
+//! and lighting outages, and obstacles.
+//!
+//! The category comes from the Q-code subject and condition when a Q) line is present,
+//! otherwise from the header keyword of FAA domestic NOTAMs (`RWY`, `TWY`, `NAV`,
+//! `OBST`) and then from words in E), closures before outages and obstacles so that
+//! `RWY 4L/22R CLSD DUE CRANE` is a closure. Designators, identifiers,
+//! frequencies, positions and heights are read from E); an obstacle without a position
+//! in E) takes the Q) line coordinates.
+
+use aviation_wx_core::{LightingKind, NavaidKind, NotamPeriod, NotamRecord, QLine};
+use regex::Regex;
+
+use crate::geometry::find_coordinates;
+use crate::lexicon::body_term;
+
+const FEET_PER_METER: f64 = 3.28084;
+
+const OUTAGE_WORDS: &[&str] = &["U/S", "UNSERVICEABLE", "OTS", "UNUSABLE"];
+const CLOSURE_WORDS: &[&str] = &["CLSD", "CLOSED"];
+const OBSTACLE_KINDS: &[&str] = &[
+    "CRANE", "CRANES", "TOWER", "MAST", "ANTENNA", "CHIMNEY", "STACK", "BLDG", "BUILDING", "TURBINE", "POLE", "TREE",
+    "TREES", "RIG",
+];
+const NOT_DESIGNATORS: &[&str] = &["AT", "TO", "IN", "ON", "OF", "BY", "OR", "BTN"];
+
+enum Category {
+    Runway,
+    Taxiway,
+    Navaid(Option<NavaidKind>),
+    Lighting(Option<LightingKind>),
+    Obstacle,
+}
+
+/// Typed records for the runway, taxiway, navaid, lighting and obstacle content of a
+/// NOTAM; empty for other subjects. `keyword` is the FAA header keyword, if any.
+pub fn extract_records(
+    qualifiers: Option<&QLine>,
+    keyword: Option<&str>,
+    e: Option<&str>,
+    period: Option<&NotamPeriod>,
+) -> Vec<NotamRecord> {
+    let text = e.unwrap_or_default().to_ascii_uppercase();
+    let tokens: Vec<&str> = text
+        .split_whitespace()
+        .map(|token| token.trim_matches(['\'', '"']).trim_end_matches(['.', ',', ';', ':']))
+        .filter(|token| !token.is_empty())
+        .collect();
+    let category = match qualifiers {
+        Some(q) => q_category(q, &tokens),
+        None => text_category(keyword, &tokens),
+    };
+
+    match category {
+        Some(Category::Runway) => designators_after(&tokens, "RWY", is_runway)
+            .into_iter()
+            .map(|runway| NotamRecord::RunwayClosure {
+                runway,
+                period: period.cloned(),
+            })
+            .collect(),
+        Some(Category::Taxiway) => designators_after(&tokens, "TWY", is_taxiway)
+            .into_iter()
+            .map(|taxiway| NotamRecord::TaxiwayClosure {
+                taxiway,
+                period: period.cloned(),
+            })
+            .collect(),
+        Some(Category::Navaid(kind)) => {
+            let Some((kind, position)) = kind.map(|kind| (kind, None)).or_else(|| {
+                tokens
+                    .iter()
+                    .enumerate()
+                    .find_map(|(idx, token)| navaid_kind(token).map(|kind| (kind, Some(idx))))
+            }) else {
+                return Vec::new();
+            };
+            vec![NotamRecord::NavaidOutage {
+                kind,
+                ident: navaid_ident(&tokens, position),
+                frequency: frequency(&text),
+                runway: designators_after(&tokens, "RWY", is_runway).into_iter().next(),
+            }]
+        }
+        Some(Category::Lighting(kind)) => vec![NotamRecord::LightingOutage {
+            kind: kind
+                .or_else(|| lighting_kind_in_text(&tokens))
+                .unwrap_or(LightingKind::Other),
+            runway: designators_after(&tokens, "RWY", is_runway).into_iter().next(),
+        }],
+        Some(Category::Obstacle) => {
+            let (latitude, longitude) = match find_coordinates(&text).first() {
+                Some((_, point)) => (Some(point.latitude), Some(point.longitude)),
+                None => (
+                    qualifiers.and_then(|q| q.latitude),
+                    qualifiers.and_then(|q| q.longitude),
+                ),
+            };
+            let (elevation_ft, height_agl_ft) = heights(&text);
+            let unlit = has_any(&tokens, &["UNLIT"])
+                || text.contains("NOT LGT")
+                || text.contains("LGT U/S")
+                || qualifiers.is_some_and(|q| q.subject == "OL");
+            let lighted = if unlit {
+                Some(false)
+            } else if has_any(&tokens, &["LGT", "LIGHTED"]) {
+                Some(true)
+            } else {
+                None
+            };
+            vec![NotamRecord::Obstacle {
+                kind: tokens
+                    .iter()
+                    .find(|token| OBSTACLE_KINDS.contains(token))
+                    .map(|token| token.to_string()),
+                latitude,
+                longitude,
+                elevation_ft,
+                height_agl_ft,
+                lighted,
+            }]
+        }
+        None => Vec::new(),
+    }
+}
+
+fn q_category(q: &QLine, tokens: &[&str]) -> Option<Category> {
+    let closed = q.condition == "LC" || has_any(tokens, CLOSURE_WORDS);
+    let outage = matches!(q.condition.as_str(), "AS" | "AU" | "AW" | "CT" | "LC") || has_outage(tokens);
+    let category = match q.subject.as_str() {
+        "MR" if closed => Category::Runway,
+        "MX" | "MY" if closed => Category::Taxiway,
+        "OB" => Category::Obstacle,
+        "OL" if outage => Category::Obstacle,
+        subject if outage => {
+            if let Some(kind) = q_navaid_kind(subject) {
+                Category::Navaid(Some(kind))
+            } else if subject.starts_with('L') {
+                Category::Lighting(Some(q_lighting_kind(subject)))
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    };
+    Some(category)
+}
+
+fn text_category(keyword: Option<&str>, tokens: &[&str]) -> Option<Category> {
+    let closed = has_any(tokens, CLOSURE_WORDS);
+    let outage = has_outage(tokens);
+    let navaid = outage && tokens.iter().any(|token| navaid_kind(token).is_some());
+    let lighting = outage && lighting_kind_in_text(tokens).is_some();
+    match keyword {
+        Some("RWY") if closed => return Some(Category::Runway),
+        Some("TWY") if closed => return Some(Category::Taxiway),
+        Some("RWY" | "TWY") if lighting => return Some(Category::Lighting(None)),
+        Some("NAV") if navaid => return Some(Category::Navaid(None)),
+        Some("OBST") => return Some(Category::Obstacle),
+        _ => {}
+    }
+    if closed && has_any(tokens, &["RWY"]) {
+        return Some(Category::Runway);
+    }
+    if closed && has_any(tokens, &["TWY"]) {
+        return Some(Category::Taxiway);
+    }
+    if navaid {
+        return Some(Category::Navaid(None));
+    }
+    if lighting {
+        return Some(Category::Lighting(None));
+    }
+    if has_any(tokens, &["OBST"]) || tokens.iter().any(|token| OBSTACLE_KINDS.contains(token)) {
+        return Some(Category::Obstacle);
+    }
+    None
+}
+
+fn q_navaid_kind(subject: &str) -> Option<NavaidKind> {
+    Some(match subject {
+        "IC" | "IS" | "IT" | "IU" => NavaidKind::Ils,
+        "IL" | "IN" => NavaidKind::Localizer,
+        "IG" => NavaidKind::GlidePath,
+        "II" | "IM" | "IO" => NavaidKind::Marker,
+        "ID" | "ND" => NavaidKind::Dme,
+        "NV" => NavaidKind::Vor,
+        "NM" => NavaidKind::VorDme,
+        "NB" | "NL" | "IX" | "IY" => NavaidKind::Ndb,
+        "NN" => NavaidKind::Tacan,
+        "NT" => NavaidKind::Vortac,
+        _ => return None,
+    })
+}
+
+fn q_lighting_kind(subject: &str) -> LightingKind {
+    match subject {
+        "LA" | "LF" | "LK" => LightingKind::Approach,
+        "LP" => LightingKind::Papi,
+        "LV" => LightingKind::Vasis,
+        "LE" | "LH" | "LL" | "LM" => LightingKind::RunwayEdge,
+        "LC" => LightingKind::RunwayCentreline,
+        "LT" => LightingKind::Threshold,
+        "LZ" => LightingKind::TouchdownZone,
+        "LX" => LightingKind::TaxiwayCentreline,
+        "LY" => LightingKind::TaxiwayEdge,
+        "LB" => LightingKind::AerodromeBeacon,
+        _ => LightingKind::Other,
+    }
+}
+
+fn navaid_kind(token: &str) -> Option<NavaidKind> {
+    Some(match token {
+        "ILS" => NavaidKind::Ils,
+        "LOC" | "LLZ" => NavaidKind::Localizer,
+        "GP" | "GS" => NavaidKind::GlidePath,
+        "OM" | "MM" | "IM" => NavaidKind::Marker,
+        "DME" => NavaidKind::Dme,
+        "VOR" => NavaidKind::Vor,
+        "VOR/DME" | "VORDME" => NavaidKind::VorDme,
+        "NDB" => NavaidKind::Ndb,
+        "TACAN" => NavaidKind::Tacan,
+        "VORTAC" => NavaidKind::Vortac,
+        _ => return None,
+    })
+}
+
+fn lighting_kind_in_text(tokens: &[&str]) -> Option<LightingKind> {
+    tokens.iter().enumerate().find_map(|(idx, token)| {
+        let next = tokens.get(idx + 1).copied().unwrap_or_default();
+        match *token {
+            "ALS" | "APCH" if *token == "ALS" || next == "LGT" => Some(LightingKind::Approach),
+            "PAPI" => Some(LightingKind::Papi),
+            "VASIS" | "VASI" => Some(LightingKind::Vasis),
+            "REDL" | "HIRL" | "MIRL" | "LIRL" => Some(LightingKind::RunwayEdge),
+            "RCLL" => Some(LightingKind::RunwayCentreline),
+            "RTHL" => Some(LightingKind::Threshold),
+            "TDZ" | "TDZL" => Some(LightingKind::TouchdownZone),
+            "ABN" => Some(LightingKind::AerodromeBeacon),
+            "LGT" | "LIGHTS" => Some(LightingKind::Other),
+            _ => None,
+        }
+    })
+}
+
+fn has_any(tokens: &[&str], words: &[&str]) -> bool {
+    tokens.iter().any(|token| words.contains(token))
+}
+
+fn has_outage(tokens: &[&str]) -> bool {
+    has_any(tokens, OUTAGE_WORDS)
+        || tokens
+            .windows(2)
+            .any(|pair| matches!(pair, ["NOT", "AVBL"] | ["OUT", "OF"]))
+}
+
+fn is_runway(token: &str) -> bool {
+    let end = |part: &str| {
+        let digits = part.chars().take_while(|ch| ch.is_ascii_digit()).count();
+        (1..=2).contains(&digits) && matches!(&part[digits..], "" | "L" | "R" | "C")
+    };
+    match token.split_once('/') {
+        Some((first, second)) => end(first) && end(second),
+        None => end(token),
+    }
+}
+
+fn is_taxiway(token: &str) -> bool {
+    let letters = token.chars().take_while(|ch| ch.is_ascii_alphabetic()).count();
+    let digits = token.len() - letters;
+    (1..=2).contains(&letters)
+        && digits <= 2
+        && token[letters..].chars().all(|ch| ch.is_ascii_digit())
+        && !NOT_DESIGNATORS.contains(&token)
+}
+
+/// Designators following each `keyword`, e.g. `TWY A, B AND C3` gives `A`, `B`, `C3`.
+fn designators_after(tokens: &[&str], keyword: &str, valid: fn(&str) -> bool) -> Vec<String> {
+    let mut designators: Vec<String> = Vec::new();
+    for (idx, _) in tokens.iter().enumerate().filter(|(_, token)| **token == keyword) {
+        for token in &tokens[idx + 1..] {
+            if matches!(*token, "AND" | "&") {
+                continue;
+            }
+            let parts: Vec<&str> = token.split(',').filter(|part| !part.is_empty()).collect();
+            if parts.is_empty() || !parts.iter().all(|part| valid(part)) {
+                break;
+            }
+            for part in parts {
+                if !designators.iter().any(|known| known == part) {
+                    designators.push(part.to_string());
+                }
+            }
+        }
+    }
+    designators
+}
+
+/// First plausible identifier after the navaid keyword at `position`, skipping runway
+/// designators, frequencies and known abbreviations.
+fn navaid_ident(tokens: &[&str], position: Option<usize>) -> Option<String> {
+    let start = position.map_or(0, |idx| idx + 1);
+    let mut skip_runway = false;
+    for token in tokens.iter().skip(start) {
+        if skip_runway {
+            skip_runway = false;
+            continue;
+        }
+        if *token == "RWY" {
+            skip_runway = true;
+            continue;
+        }
+        let alphabetic = (2..=4).contains(&token.len()) && token.chars().all(|ch| ch.is_ascii_alphabetic());
+        if alphabetic && navaid_kind(token).is_none() && body_term(token).is_none() && !NOT_DESIGNATORS.contains(token) {
+            return Some(token.to_string());
+        }
+        if OUTAGE_WORDS.contains(token) {
+            break;
+        }
+    }
+    None
+}
+
+fn frequency(text: &str) -> Option<String> {
+    let re = Regex::new(r"\b(\d{2,4}(?:\.\d{1,3})?)\s?(MHZ|KHZ)\b").unwrap();
+    re.captures(text).map(|caps| format!("{}{}", &caps[1], &caps[2]))
+}
+
+/// Elevation (AMSL) and height (AGL) in feet from `150FT AGL`, `230FT AMSL`, `70M AGL`.
+fn heights(text: &str) -> (Option<u32>, Option<u32>) {
+    let re = Regex::new(r"\b(\d{1,5})\s?(FT|M)\s?(AMSL|MSL|AGL)\b").unwrap();
+    let mut elevation = None;
+    let mut height = None;
+    for caps in re.captures_iter(text) {
+        let Ok(value) = caps[1].parse::<u32>() else {
+            continue;
+        };
+        let feet = if &caps[2] == "M" {
+            (f64::from(value) * FEET_PER_METER).round() as u32
+        } else {
+            value
+        };
+        match &caps[3] {
+            "AGL" => height = height.or(Some(feet)),
+            _ => elevation = elevation.or(Some(feet)),
+        }
+    }
+    (elevation, height)
+}
+
+/// Chinese summary of one record for explanations.
+pub(crate) fn describe_record(record: &NotamRecord) -> String {
+    let runway_suffix = |runway: &Option<String>| runway.as_ref().map(|rwy| format!("（跑道 {}）", rwy)).unwrap_or_default();
+    match record {
+        NotamRecord::RunwayClosure { runway, .. } => format!("跑道 {} 关闭", runway),
+        NotamRecord::TaxiwayClosure { taxiway, .. } => format!("滑行道 {} 关闭", taxiway),
+        NotamRecord::NavaidOutage {
+            kind,
+            ident,
+            frequency,
+            runway,
+        } => {
+            let mut parts = vec![navaid_label(*kind).to_string()];
+            parts.extend(ident.clone());
+            parts.extend(frequency.clone());
+            format!("{} 不可用{}", parts.join(" "), runway_suffix(runway))
+        }
+        NotamRecord::LightingOutage { kind, runway } => {
+            format!("{} 不可用{}", lighting_label(*kind), runway_suffix(runway))
+        }
+        NotamRecord::Obstacle {
+            kind,
+            elevation_ft,
+            height_agl_ft,
+            lighted,
+            ..
+        } => {
+            let mut parts = vec![format!("障碍物{}", kind.as_ref().map(|kind| format!(" {}", kind)).unwrap_or_default())];
+            if let Some(elevation) = elevation_ft {
+                parts.push(format!("标高 {} 英尺", elevation));
+            }
+            if let Some(height) = height_agl_ft {
+                parts.push(format!("离地高 {} 英尺", height));
+            }
+            match lighted {
+                Some(true) => parts.push("有灯光".to_string()),
+                Some(false) => parts.push("无灯光".to_string()),
+                None => {}
+            }
+            parts.join(" ")
+        }
+    }
+}
+
+fn navaid_label(kind: NavaidKind) -> &'static str {
+    match kind {
+        NavaidKind::Ils => "ILS",
+        NavaidKind::Localizer => "航向信标",
+        NavaidKind::GlidePath => "下滑道",
+        NavaidKind::Marker => "指点标",
+        NavaidKind::Vor => "VOR",
+        NavaidKind::Dme => "DME",
+        NavaidKind::VorDme => "VOR/DME",
+        NavaidKind::Ndb => "NDB",
+        NavaidKind::Tacan => "TACAN",
+        NavaidKind::Vortac => "VORTAC",
+    }
+}
+
+fn lighting_label(kind: LightingKind) -> &'static str {
+    match kind {
+        LightingKind::Approach => "进近灯",
+        LightingKind::Papi => "PAPI",
+        LightingKind::Vasis => "VASIS",
+        LightingKind::RunwayEdge => "跑道边灯",
+        LightingKind::RunwayCentreline => "跑道中线灯",
+        LightingKind::Threshold => "跑道入口灯",
+        LightingKind::TouchdownZone => "接地带灯",
+        LightingKind::TaxiwayCentreline => "滑行道中线灯",
+        LightingKind::TaxiwayEdge => "滑行道边灯",
+        LightingKind::AerodromeBeacon => "机场灯标",
+        LightingKind::Other => "灯光",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_notam, parse_notam};
+
+    fn records(raw: &str) -> Vec<NotamRecord> {
+        normalize_notam(&parse_notam(raw).0).records
+    }
+
+    #[test]
+    fn extracts_closures_and_outages_from_q_codes() {
+        let closure = records(
+            "A1234/24 NOTAMN Q) ZSHA/QMRLC/IV/NBO/A/000/999/3112N12130E005 A) ZSPD B) 2402010000 C) 2402012359 \
+             E) RWY 17L/35R CLSD",
+        );
+        assert!(matches!(
+            &closure[..],
+            [NotamRecord::RunwayClosure { runway, period: Some(_) }] if runway == "17L/35R"
+        ));
+
+        let vor = records(
+            "A1235/24 NOTAMN Q) ZBPE/QNVAS/IV/BO/E/000/999/4004N11635E025 A) ZBAA B) 2402010000 C) 2402020000 \
+             E) VOR 'PEK' 114.7MHZ U/S",
+        );
+        assert_eq!(
+            vor,
+            vec![NotamRecord::NavaidOutage {
+                kind: NavaidKind::Vor,
+                ident: Some("PEK".to_string()),
+                frequency: Some("114.7MHZ".to_string()),
+                runway: None,
+            }]
+        );
+
+        let papi = records(
+            "A1236/24 NOTAMN Q) ZBPE/QLPAS/IV/BO/A/000/999/4004N11635E005 A) ZBAA B) 2402010000 C) 2402020000 \
+             E) RWY 36R PAPI U/S",
+        );
+        assert_eq!(
+            papi,
+            vec![NotamRecord::LightingOutage {
+                kind: LightingKind::Papi,
+                runway: Some("36R".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_e_text_without_q_line() {
+        let ils = records("!JFK 01/120 JFK NAV ILS RWY 4R IHIQ U/S 2401011200-2401011800");
+        assert_eq!(
+            ils,
+            vec![NotamRecord::NavaidOutage {
+                kind: NavaidKind::Ils,
+                ident: Some("IHIQ".to_string()),
+                frequency: None,
+                runway: Some("4R".to_string()),
+            }]
+        );
+
+        let taxiways = records("!JFK 01/121 JFK TWY A, B AND K3 CLSD 2401011200-2401011800");
+        let names: Vec<&str> = taxiways
+            .iter()
+            .filter_map(|record| match record {
+                NotamRecord::TaxiwayClosure { taxiway, .. } => Some(taxiway.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, vec!["A", "B", "K3"]);
+
+        assert!(records("!JFK 01/122 JFK SVC FUEL NOT AVBL 2401011200-2401011800").is_empty());
+    }
+
+    #[test]
+    fn prefers_faa_keyword_and_closures_in_e_text() {
+        let closure = records("!JFK 01/123 JFK RWY 4L/22R CLSD DUE CRANE 2401011200-2401011800");
+        assert!(matches!(
+            &closure[..],
+            [NotamRecord::RunwayClosure { runway, .. }] if runway == "4L/22R"
+        ));
+
+        let ils = records("!JFK 01/124 JFK NAV RWY 22L ILS U/S WHILE RWY 4L/22R CLSD 2401011200-2401011800");
+        assert!(matches!(
+            &ils[..],
+            [NotamRecord::NavaidOutage { kind: NavaidKind::Ils, runway: Some(runway), .. }] if runway == "22L"
+        ));
+    }
+
+    #[test]
+    fn extracts_obstacle_position_and_heights() {
+        let obstacle = records(
+            "B0002/24 NOTAMN Q) EGTT/QOBCE/IV/M/A/000/010/5129N00028W005 A) EGLL B) 2402010000 C) PERM \
+             E) CRANE ERECTED PSN 512830N0002745W HGT 150FT AGL ELEV 230FT AMSL LGT",
+        );
+        let [NotamRecord::Obstacle {
+            kind,
+            latitude,
+            longitude,
+            elevation_ft,
+            height_agl_ft,
+            lighted,
+        }] = &obstacle[..]
+        else {
+            panic!("expected one obstacle: {:?}", obstacle);
+        };
+        assert_eq!(kind.as_deref(), Some("CRANE"));
+        assert!((latitude.unwrap() - 51.475).abs() < 1e-6);
+        assert!((longitude.unwrap() + 0.4625).abs() < 1e-6);
+        assert_eq!((*elevation_ft, *height_agl_ft, *lighted), (Some(230), Some(150), Some(true)));
+
+        let fallback = records("!BOS 03/007 BOS OBST TOWER 1149FT AMSL NOT LGT 2401011200-2401011800");
+        assert!(matches!(
+            &fallback[..],
+            [NotamRecord::Obstacle { latitude: None, elevation_ft: Some(1149), lighted: Some(false), .. }]
+        ));
+    }
+}
@@ -0,0 +1,396 @@
+//! NOTAM D) schedules and their expansion into active intervals.
+//!
+//! Supported forms, combinable and separated by spaces or commas:
+//!
+//! - `H24`, `DLY 0600-1800`, `0600-0800 1400-1600`
+//! - weekdays: `MON-FRI 2200-0500`, `MON WED FRI 0800-1200`
+//! - dates: `JAN 05 10 15 0800-1200`, `01-05 0900-1100` (any month)
+//! - daylight: `SR-SS`, `HJ` (sunrise to sunset), `HN` (sunset to sunrise), `SR-1800`
+//!
+//! A time range ending at or before its start runs into the next day. Sunrise and
+//! sunset are computed for the Q) line coordinates; without them such ranges are
+//! not expanded.
+
+use aviation_wx_core::{NotamDateTime, NotamInterval, NotamNormalized, NotamSchedule, ScheduleRule, ScheduleTime, ScheduleTimeRange};
+
+const WEEKDAYS: [&str; 7] = ["MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const DAY_MINUTES: i64 = 1440;
+/// How far a NOTAM without an end time (`PERM`, missing C)) is expanded.
+const OPEN_ENDED_DAYS: i64 = 366;
+
+/// Parses a D) field. Unknown items are reported as unrecognized tokens and skipped;
+/// `None` is returned when nothing usable remains.
+pub fn parse_schedule(raw: &str) -> (Option<NotamSchedule>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let text = raw.to_ascii_uppercase().replace(',', " , ").replace(" - ", "-");
+    let mut rules = Vec::new();
+    let mut awaiting: Vec<ScheduleRule> = Vec::new();
+    let mut current = empty_rule();
+
+    for token in text.split_whitespace() {
+        if token == "," {
+            finish(&mut rules, &mut awaiting, &mut current);
+            continue;
+        }
+        if let Some(times) = parse_time_range(token) {
+            current.times.push(times);
+            continue;
+        }
+        let is_day_token = token == "DLY" || token == "DAILY" || weekdays(token).is_some() || month(token).is_some() || dates(token).is_some();
+        if !is_day_token {
+            warnings.push(format!("Unrecognized token: {}", token));
+            continue;
+        }
+        if !current.times.is_empty() {
+            finish(&mut rules, &mut awaiting, &mut current);
+        }
+        if let Some(days) = weekdays(token) {
+            current.weekdays.extend(days);
+        } else if let Some(month) = month(token) {
+            if current.month.is_some() {
+                awaiting.push(std::mem::replace(&mut current, empty_rule()));
+            }
+            current.month = Some(month);
+        } else if let Some(days) = dates(token) {
+            current.dates.extend(days);
+        }
+    }
+    finish(&mut rules, &mut awaiting, &mut current);
+
+    if rules.is_empty() {
+        return (None, warnings);
+    }
+    let schedule = NotamSchedule {
+        rules,
+        raw: raw.trim().to_string(),
+    };
+    (Some(schedule), warnings)
+}
+
+fn empty_rule() -> ScheduleRule {
+    ScheduleRule {
+        weekdays: Vec::new(),
+        month: None,
+        dates: Vec::new(),
+        times: Vec::new(),
+    }
+}
+
+fn finish(rules: &mut Vec<ScheduleRule>, awaiting: &mut Vec<ScheduleRule>, current: &mut ScheduleRule) {
+    let mut rule = std::mem::replace(current, empty_rule());
+    let has_days = !rule.weekdays.is_empty() || rule.month.is_some() || !rule.dates.is_empty();
+    if rule.times.is_empty() && !has_days && awaiting.is_empty() {
+        return;
+    }
+    if rule.times.is_empty() {
+        rule.times.push(whole_day());
+    }
+    for mut pending in awaiting.drain(..) {
+        pending.times = rule.times.clone();
+        rules.push(pending);
+    }
+    if has_days || !rule.times.is_empty() {
+        rules.push(rule);
+    }
+}
+
+fn whole_day() -> ScheduleTimeRange {
+    ScheduleTimeRange {
+        start: ScheduleTime::Utc(0),
+        end: ScheduleTime::Utc(DAY_MINUTES as u16),
+    }
+}
+
+fn parse_time_range(token: &str) -> Option<ScheduleTimeRange> {
+    match token {
+        "H24" => return Some(whole_day()),
+        "HJ" => {
+            return Some(ScheduleTimeRange {
+                start: ScheduleTime::Sunrise,
+                end: ScheduleTime::Sunset,
+            })
+        }
+        "HN" => {
+            return Some(ScheduleTimeRange {
+                start: ScheduleTime::Sunset,
+                end: ScheduleTime::Sunrise,
+            })
+        }
+        _ => {}
+    }
+    let (start, end) = token.split_once('-')?;
+    Some(ScheduleTimeRange {
+        start: parse_time(start)?,
+        end: parse_time(end)?,
+    })
+}
+
+fn parse_time(value: &str) -> Option<ScheduleTime> {
+    match value {
+        "SR" => Some(ScheduleTime::Sunrise),
+        "SS" => Some(ScheduleTime::Sunset),
+        _ if value.len() == 4 && value.chars().all(|ch| ch.is_ascii_digit()) => {
+            let hour: u16 = value[..2].parse().ok()?;
+            let minute: u16 = value[2..].parse().ok()?;
+            let total = hour * 60 + minute;
+            (minute < 60 && total <= DAY_MINUTES as u16).then_some(ScheduleTime::Utc(total))
+        }
+        _ => None,
+    }
+}
+
+fn weekday(value: &str) -> Option<u8> {
+    WEEKDAYS.iter().position(|name| *name == value).map(|idx| idx as u8)
+}
+
+fn weekdays(token: &str) -> Option<Vec<u8>> {
+    if let Some(day) = weekday(token) {
+        return Some(vec![day]);
+    }
+    let (from, to) = token.split_once('-')?;
+    let (from, to) = (weekday(from)?, weekday(to)?);
+    let span = (to + 7 - from) % 7;
+    Some((0..=span).map(|offset| (from + offset) % 7).collect())
+}
+
+fn month(token: &str) -> Option<u8> {
+    MONTHS.iter().position(|name| *name == token).map(|idx| idx as u8 + 1)
+}
+
+fn dates(token: &str) -> Option<Vec<u8>> {
+    let day = |value: &str| -> Option<u8> {
+        let day: u8 = (value.len() == 2).then(|| value.parse().ok())??;
+        (1..=31).contains(&day).then_some(day)
+    };
+    if let Some(single) = day(token) {
+        return Some(vec![single]);
+    }
+    let (from, to) = token.split_once('-')?;
+    let (from, to) = (day(from)?, day(to)?);
+    (from <= to).then(|| (from..=to).collect())
+}
+
+/// Expands `schedule` into the intervals it is active within `[from, to)`.
+/// `position` is `(latitude, longitude)` in decimal degrees, used for sunrise/sunset.
+pub fn expand_schedule(
+    schedule: &NotamSchedule,
+    from: NotamDateTime,
+    to: NotamDateTime,
+    position: Option<(f64, f64)>,
+) -> Vec<NotamInterval> {
+    let (from_min, to_min) = (from.to_minutes(), to.to_minutes());
+    let mut spans: Vec<(i64, i64)> = Vec::new();
+    let first_day = from_min.div_euclid(DAY_MINUTES) - 1;
+    let last_day = to_min.div_euclid(DAY_MINUTES);
+    for day in first_day..=last_day {
+        let midnight = day * DAY_MINUTES;
+        let date = NotamDateTime::from_minutes(midnight);
+        for rule in schedule.rules.iter().filter(|rule| rule_matches(rule, &date)) {
+            for range in &rule.times {
+                let Some(start) = time_offset(range.start, &date, position) else {
+                    continue;
+                };
+                let Some(mut end) = time_offset(range.end, &date, position) else {
+                    continue;
+                };
+                if end <= start {
+                    let next = NotamDateTime::from_minutes(midnight + DAY_MINUTES);
+                    match time_offset(range.end, &next, position) {
+                        Some(next_end) => end = next_end + DAY_MINUTES,
+                        None => continue,
+                    }
+                }
+                let (start, end) = ((midnight + start).max(from_min), (midnight + end).min(to_min));
+                if start < end {
+                    spans.push((start, end));
+                }
+            }
+        }
+    }
+
+    spans.sort();
+    let mut merged: Vec<(i64, i64)> = Vec::new();
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+        .into_iter()
+        .map(|(start, end)| NotamInterval {
+            start: NotamDateTime::from_minutes(start),
+            end: NotamDateTime::from_minutes(end),
+        })
+        .collect()
+}
+
+fn rule_matches(rule: &ScheduleRule, date: &NotamDateTime) -> bool {
+    (rule.weekdays.is_empty() || rule.weekdays.contains(&date.weekday()))
+        && rule.month.is_none_or(|month| month == date.month)
+        && (rule.dates.is_empty() || rule.dates.contains(&date.day))
+}
+
+fn time_offset(time: ScheduleTime, date: &NotamDateTime, position: Option<(f64, f64)>) -> Option<i64> {
+    match time {
+        ScheduleTime::Utc(minutes) => Some(minutes as i64),
+        ScheduleTime::Sunrise => sun_event(date, position?, true),
+        ScheduleTime::Sunset => sun_event(date, position?, false),
+    }
+}
+
+/// Sunrise or sunset on the local date `date` as minutes after `date` 00:00Z, which may
+/// be negative east of Greenwich. `None` during polar day or night. Uses the
+/// Almanac for Computers algorithm with the official 90°50' zenith (about ±2 min).
+fn sun_event(date: &NotamDateTime, (latitude, longitude): (f64, f64), rise: bool) -> Option<i64> {
+    let jan1 = NotamDateTime::new(date.year, 1, 1, 0, 0)?;
+    let day_of_year = ((date.to_minutes() - jan1.to_minutes()) / DAY_MINUTES + 1) as f64;
+    let lng_hour = longitude / 15.0;
+    let t = day_of_year + ((if rise { 6.0 } else { 18.0 }) - lng_hour) / 24.0;
+
+    let mean_anomaly = 0.9856 * t - 3.289;
+    let true_longitude = (mean_anomaly
+        + 1.916 * mean_anomaly.to_radians().sin()
+        + 0.020 * (2.0 * mean_anomaly).to_radians().sin()
+        + 282.634)
+        .rem_euclid(360.0);
+    let mut right_ascension = (0.91764 * true_longitude.to_radians().tan())
+        .atan()
+        .to_degrees()
+        .rem_euclid(360.0);
+    right_ascension += (true_longitude / 90.0).floor() * 90.0 - (right_ascension / 90.0).floor() * 90.0;
+    let right_ascension = right_ascension / 15.0;
+
+    let sin_dec = 0.39782 * true_longitude.to_radians().sin();
+    let cos_dec = sin_dec.asin().cos();
+    let cos_h = (90.833_f64.to_radians().cos() - sin_dec * latitude.to_radians().sin())
+        / (cos_dec * latitude.to_radians().cos());
+    if !(-1.0..=1.0).contains(&cos_h) {
+        return None;
+    }
+    let hour_angle = if rise {
+        360.0 - cos_h.acos().to_degrees()
+    } else {
+        cos_h.acos().to_degrees()
+    } / 15.0;
+    let local_mean_time = (hour_angle + right_ascension - 0.06571 * t - 6.622).rem_euclid(24.0);
+    Some(((local_mean_time - lng_hour) * 60.0).round() as i64)
+}
+
+/// Active intervals of a NOTAM: its D) schedule expanded over B)–C), or the whole
+/// period when there is no schedule. Open-ended NOTAMs are expanded for one year.
+pub fn active_intervals(notam: &NotamNormalized) -> Vec<NotamInterval> {
+    let Some(period) = &notam.period else {
+        return Vec::new();
+    };
+    let end = period
+        .end
+        .unwrap_or_else(|| NotamDateTime::from_minutes(period.start.to_minutes() + OPEN_ENDED_DAYS * DAY_MINUTES));
+    if end <= period.start {
+        return Vec::new();
+    }
+    match &notam.schedule {
+        Some(schedule) => expand_schedule(schedule, period.start, end, position(notam)),
+        None => vec![NotamInterval {
+            start: period.start,
+            end,
+        }],
+    }
+}
+
+/// Whether the NOTAM is in force at `time`, honouring its D) schedule.
+pub fn is_active_at(notam: &NotamNormalized, time: &NotamDateTime) -> bool {
+    let Some(period) = &notam.period else {
+        return false;
+    };
+    if !period.contains(time) {
+        return false;
+    }
+    let Some(schedule) = &notam.schedule else {
+        return true;
+    };
+    let minutes = time.to_minutes();
+    let from = NotamDateTime::from_minutes(minutes - DAY_MINUTES);
+    let to = NotamDateTime::from_minutes(minutes + DAY_MINUTES);
+    expand_schedule(schedule, from, to, position(notam))
+        .iter()
+        .any(|interval| interval.start <= *time && *time < interval.end)
+}
+
+fn position(notam: &NotamNormalized) -> Option<(f64, f64)> {
+    let q = notam.qualifiers.as_ref()?;
+    Some((q.latitude?, q.longitude?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(year: u16, month: u8, day: u8, hour: u8, minute: u8) -> NotamDateTime {
+        NotamDateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    fn spans(raw: &str, from: NotamDateTime, to: NotamDateTime) -> Vec<String> {
+        let (schedule, warnings) = parse_schedule(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        expand_schedule(&schedule.expect("schedule"), from, to, Some((51.5, 0.0)))
+            .iter()
+            .map(|interval| format!("{}/{}", interval.start, interval.end))
+            .collect()
+    }
+
+    #[test]
+    fn parses_common_forms() {
+        let (schedule, warnings) = parse_schedule("JAN 05 10 15 0800-1200, MON-FRI 2200-0500 SAT SUN H24");
+        assert!(warnings.is_empty());
+        let rules = schedule.expect("schedule").rules;
+        assert_eq!(rules.len(), 3);
+        assert_eq!((rules[0].month, rules[0].dates.clone()), (Some(1), vec![5, 10, 15]));
+        assert_eq!(rules[1].weekdays, vec![0, 1, 2, 3, 4]);
+        assert_eq!(rules[1].times[0].end, ScheduleTime::Utc(300));
+        assert_eq!(rules[2].weekdays, vec![5, 6]);
+        assert_eq!(rules[2].times, vec![whole_day()]);
+
+        let (_, warnings) = parse_schedule("DLY 0600-1800 EXC HOL");
+        assert_eq!(warnings, vec!["Unrecognized token: EXC", "Unrecognized token: HOL"]);
+    }
+
+    #[test]
+    fn expands_overnight_and_weekday_ranges() {
+        // 2024-02-02 is a Friday.
+        let result = spans("MON-FRI 2200-0500", at(2024, 2, 2, 0, 0), at(2024, 2, 6, 0, 0));
+        assert_eq!(
+            result,
+            vec![
+                "2024-02-02 00:00Z/2024-02-02 05:00Z",
+                "2024-02-02 22:00Z/2024-02-03 05:00Z",
+                "2024-02-05 22:00Z/2024-02-06 00:00Z",
+            ]
+        );
+        let daily = spans("DLY 0600-0800 0700-0900", at(2024, 2, 1, 0, 0), at(2024, 2, 2, 0, 0));
+        assert_eq!(daily, vec!["2024-02-01 06:00Z/2024-02-01 09:00Z"]);
+    }
+
+    #[test]
+    fn sunrise_sunset_for_q_line_position() {
+        let result = spans("SR-SS", at(2024, 3, 20, 0, 0), at(2024, 3, 21, 0, 0));
+        assert_eq!(result.len(), 1);
+        let interval = &result[0];
+        assert!(interval.starts_with("2024-03-20 06:0"), "{}", interval);
+        assert!(interval.contains("/2024-03-20 18:1"), "{}", interval);
+    }
+
+    #[test]
+    fn activity_follows_period_and_schedule() {
+        let (parsed, _) = crate::parse_notam(
+            "A0001/24 NOTAMN Q) EGTT/QMRLC/IV/NBO/A/000/999/5129N00028W005 A) EGLL \
+             B) 2402010000 C) 2402292359 D) MON-FRI 0800-1600 E) RWY 09L/27R CLSD",
+        );
+        let notam = crate::normalize_notam(&parsed);
+        assert!(is_active_at(&notam, &at(2024, 2, 5, 9, 0)));
+        assert!(!is_active_at(&notam, &at(2024, 2, 4, 9, 0)));
+        assert!(!is_active_at(&notam, &at(2024, 3, 4, 9, 0)));
+        assert_eq!(active_intervals(&notam).len(), 21);
+    }
+}
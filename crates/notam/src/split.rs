@@ -0,0 +1,175 @@
+//! Splitting of NOTAM bulletins and pre-flight information bulletins (PIB) into
+//! individual NOTAMs.
+//!
+//! A NOTAM starts at a header line (`A1234/24 NOTAMN`, or `!JFK 01/123` for FAA
+//! domestic NOTAMs) or, outside an open parenthesized NOTAM, a line opening with `(` or
+//! the full-width `（`. A parenthesized NOTAM ends where its parentheses balance,
+//! ignoring the `)` of field tags; any other NOTAM ends at the next start or blank line,
+//! and text after a blank line that only continues its fields (B) to G), no header, Q)
+//! or A)) is kept with it. Text between NOTAMs without a Q), A) or E) tag (PIB
+//! headings, page footers) is skipped.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Byte range `[start, end)` of one NOTAM in the bulletin text, without surrounding
+/// whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotamSegment {
+    pub start: usize,
+    pub end: usize,
+}
+
+struct OpenSegment {
+    start: usize,
+    end: usize,
+    depth: Option<i32>,
+}
+
+/// Finds the NOTAMs in `text`, in order of appearance.
+pub fn split_notams(text: &str) -> Vec<NotamSegment> {
+    let header_re =
+        Regex::new(r"^(?:\(?\s*[A-Z]\d{4}/\d{2}\s+NOTAM[NRC]\b|![A-Z0-9]{2,5}\s+\d{1,2}/\d{1,4}\b)").unwrap();
+    let tag_re = Regex::new(r"(?:^|[\s(])[QAE]\)").unwrap();
+    let start_tag_re = Regex::new(r"(?:^|[\s(])[QA]\)").unwrap();
+    let field_re = Regex::new(r"(?:^|[\s(])[B-G]\)").unwrap();
+
+    let mut candidates = Vec::new();
+    let mut current: Option<OpenSegment> = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start_matches(|ch: char| ch.is_whitespace() || ch == '\u{feff}');
+        let lead = line.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        if trimmed.is_empty() {
+            if current.as_ref().is_some_and(|open| open.depth.is_none()) {
+                candidates.extend(current.take());
+            }
+            continue;
+        }
+
+        let upper = trimmed.to_ascii_uppercase();
+        let opens = upper.starts_with(['(', '（']) && current.as_ref().is_none_or(|open| open.depth.is_none());
+        if header_re.is_match(&upper) || opens {
+            candidates.extend(current.take());
+        }
+        let open = current.get_or_insert(OpenSegment {
+            start: line_start + lead,
+            end: line_start + lead,
+            depth: opens.then_some(0),
+        });
+        open.end = line_start + lead + trimmed.len();
+        if open.depth.is_some() {
+            let depth = paren_balance(&text[open.start..open.end]);
+            open.depth = Some(depth);
+            if depth <= 0 {
+                candidates.extend(current.take());
+            }
+        }
+    }
+    candidates.extend(current);
+
+    let mut notams: Vec<OpenSegment> = Vec::new();
+    for segment in candidates {
+        let upper = text[segment.start..segment.end].to_ascii_uppercase();
+        let header = header_re.is_match(&upper);
+        let continues = !header && !start_tag_re.is_match(&upper) && field_re.is_match(&upper);
+        match notams.last_mut() {
+            Some(last) if continues && last.depth.is_none() => last.end = segment.end,
+            _ if header || tag_re.is_match(&upper) => notams.push(segment),
+            _ => {}
+        }
+    }
+    notams
+        .into_iter()
+        .map(|segment| NotamSegment {
+            start: segment.start,
+            end: segment.end,
+        })
+        .collect()
+}
+
+/// Opening minus closing parentheses in the text of a NOTAM, not counting the `)` of the
+/// field tags `parse_notam` reads.
+fn paren_balance(notam: &str) -> i32 {
+    let upper = crate::fold_width(notam).to_ascii_uppercase();
+    let tags: Vec<usize> = crate::field_tags(&upper).iter().map(|(pos, _)| pos + 1).collect();
+    let mut balance = 0;
+    for (idx, ch) in upper.char_indices() {
+        match ch {
+            '(' => balance += 1,
+            ')' if !tags.contains(&idx) => balance -= 1,
+            _ => {}
+        }
+    }
+    balance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pieces(text: &str) -> Vec<&str> {
+        split_notams(text)
+            .iter()
+            .map(|segment| &text[segment.start..segment.end])
+            .collect()
+    }
+
+    #[test]
+    fn splits_parenthesized_pib_and_skips_headings() {
+        let pib = "PRE-FLIGHT INFORMATION BULLETIN\nZBAA BEIJING/CAPITAL\n\
+                   (A0100/24 NOTAMN\nQ) ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005\n\
+                   A) ZBAA B) 2402010000 C) 2402100000\n\nE) RWY 01/19 CLSD (SEE AIP AD 2.9))\n\
+                   (A0101/24 NOTAMR A0090/24 Q) ZBPE/QMXLC/IV/BO/A/000/999/4004N11635E005 \
+                   A) ZBAA B) 2402010000 C) 2402030000 E) TWY A CLSD)\n-- END OF PIB --\n";
+        let notams = pieces(pib);
+        assert_eq!(notams.len(), 2);
+        assert!(notams[0].starts_with("(A0100/24") && notams[0].ends_with("AD 2.9))"));
+        assert!(notams[1].starts_with("(A0101/24") && notams[1].ends_with("TWY A CLSD)"));
+    }
+
+    #[test]
+    fn splits_on_headers_and_blank_lines() {
+        let bulletin = "B0001/24 NOTAMN A) EGLL B) 2402010000 C) 2402020000 E) RWY 09L CLSD\n\
+                        B0002/24 NOTAMN A) EGLL B) 2402010000 C) PERM\nE) CRANE ERECTED\n\n\
+                        A) EGKK B) 2402010000 C) 2402020000 E) ILS RWY 26L U/S\n\
+                        !JFK 01/123 JFK RWY 4L/22R CLSD 2401011200-2401011800\n";
+        let segments = split_notams(bulletin);
+        let notams = pieces(bulletin);
+        assert_eq!(notams.len(), 4);
+        assert_eq!(segments[0].start, 0);
+        assert!(notams[1].ends_with("CRANE ERECTED"));
+        assert!(notams[2].starts_with("A) EGKK") && notams[2].ends_with("U/S"));
+        assert!(notams[3].starts_with("!JFK"));
+        assert_eq!(pieces("RWY 09L CLSD"), Vec::<&str>::new());
+
+        let gapped = "B0002/24 NOTAMN A) EGLL B) 2402010000 C) PERM\n\nE) CRANE ERECTED";
+        assert_eq!(pieces(gapped), vec![gapped]);
+    }
+
+    #[test]
+    fn keeps_parenthesized_lines_inside_an_open_notam() {
+        let pib = "(A0100/24 NOTAMN\nQ) ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005\n\
+                   A) ZBAA B) 2402010000 C) 2402100000\nE) RWY 01/19 CLSD\n(SEE AIP AD 2.9))\n\
+                   (A0101/24 NOTAMN A) ZBAA B) 2402010000 C) 2402030000 E) TWY A CLSD)";
+        let notams = pieces(pib);
+        assert_eq!(notams.len(), 2);
+        assert!(notams[0].ends_with("(SEE AIP AD 2.9))"));
+        assert!(notams[1].starts_with("(A0101/24"));
+    }
+
+    #[test]
+    fn reads_letters_before_parentheses_in_e_as_text() {
+        let pib = "(A0102/24 NOTAMN A) ZBAA B) 2402010000 C) 2402030000\nE) ILS RWY 01 (CAT B)\nU/S)\n\
+                   (A0103/24 NOTAMN A) ZBAA B) 2402010000 C) 2402030000 E) TWY B CLSD)";
+        let notams = pieces(pib);
+        assert_eq!(notams.len(), 2);
+        assert!(notams[0].ends_with("U/S)"));
+        let (parsed, _) = crate::parse_notam(notams[0]);
+        assert_eq!(parsed.b.as_deref(), Some("2402010000"));
+        assert_eq!(parsed.e.as_deref(), Some("ILS RWY 01 (CAT B)\nU/S"));
+    }
+}
@@ -0,0 +1,290 @@
+//! In-memory set of NOTAMs in force, maintained from a stream of decoded NOTAMs.
+//!
+//! `NOTAMR` replaces the referenced NOTAM and `NOTAMC` removes it. Identifiers that
+//! have been replaced or cancelled are remembered, so a late copy of a superseded
+//! NOTAM is not resurrected. A NOTAM whose C) is `EST` stays in force after that time
+//! until it is replaced or cancelled.
+//!
+//! Series and numbers are only unique within the issuing NOTAM office, so NOTAMs are
+//! keyed by the nationality letters of their Q) FIR (or first A) location) and their
+//! identifier: `A1234/24` from ZBPE and from EGTT are different NOTAMs. FAA domestic
+//! NOTAMs have no ICAO header and are keyed by accountability and number (`JFK 01/123`).
+
+use std::collections::{HashMap, HashSet};
+
+use aviation_wx_core::{issue, Issue, NotamDateTime, NotamId, NotamKind, NotamNormalized};
+
+use crate::schedule::is_active_at;
+use crate::{normalize_notam, parse_notam};
+
+/// Key of a stored NOTAM.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum NotamKey {
+    /// Issuing region and identifier.
+    Icao(String, NotamId),
+    /// Accountability and `MM/NNN` number of an FAA domestic NOTAM.
+    Faa(String, String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NotamStore {
+    notams: HashMap<NotamKey, NotamNormalized>,
+    superseded: HashSet<NotamKey>,
+}
+
+impl NotamStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses and ingests a single raw NOTAM.
+    pub fn ingest_raw(&mut self, raw: &str) -> Vec<Issue> {
+        let (parsed, _) = parse_notam(raw);
+        self.ingest(normalize_notam(&parsed))
+    }
+
+    /// Applies `notam` to the store. NOTAMs without an ICAO or FAA domestic header cannot
+    /// be tracked and are rejected with `notam_header_missing`.
+    pub fn ingest(&mut self, notam: NotamNormalized) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let Some(header) = notam.header.clone() else {
+            match notam.faa_header.clone() {
+                Some(faa) => {
+                    let key = NotamKey::Faa(faa.accountability.to_ascii_uppercase(), faa.number.clone());
+                    let label = format!("{} {}", faa.accountability, faa.number);
+                    self.insert(key, &label, notam, &mut issues);
+                }
+                None => issues.push(issue("notam_header_missing", "NOTAM without a header cannot be stored.", None)),
+            }
+            return issues;
+        };
+        let region = issuing_region(&notam);
+        let key = NotamKey::Icao(region.clone(), header.id.clone());
+        if self.superseded.contains(&key) {
+            issues.push(issue(
+                "notam_superseded",
+                format!("{} was already replaced or cancelled.", header.id),
+                Some(header.id.to_string()),
+            ));
+            return issues;
+        }
+
+        if matches!(header.kind, NotamKind::Replace | NotamKind::Cancel) {
+            let Some(reference) = &header.references else {
+                issues.push(issue(
+                    "notam_reference_missing",
+                    format!("{} does not name the NOTAM it replaces or cancels.", header.id),
+                    Some(header.id.to_string()),
+                ));
+                return issues;
+            };
+            let key = NotamKey::Icao(region, reference.clone());
+            if self.notams.remove(&key).is_none() && !self.superseded.contains(&key) {
+                issues.push(issue(
+                    "notam_reference_unknown",
+                    format!("{} references {}, which is not in the store.", header.id, reference),
+                    Some(reference.to_string()),
+                ));
+            }
+            self.superseded.insert(key);
+        }
+
+        if header.kind == NotamKind::Cancel {
+            self.superseded.insert(key);
+            return issues;
+        }
+        self.insert(key, &header.id.to_string(), notam, &mut issues);
+        issues
+    }
+
+    fn insert(&mut self, key: NotamKey, label: &str, notam: NotamNormalized, issues: &mut Vec<Issue>) {
+        if self.notams.insert(key, notam).is_some() {
+            issues.push(issue(
+                "notam_duplicate",
+                format!("{} was already stored and has been overwritten.", label),
+                Some(label.to_string()),
+            ));
+        }
+    }
+
+    /// NOTAM `id` issued in `region`, the nationality letters of its FIR (e.g. `Z`, `EG`).
+    pub fn get(&self, region: &str, id: &NotamId) -> Option<&NotamNormalized> {
+        self.notams.get(&NotamKey::Icao(region.to_ascii_uppercase(), id.clone()))
+    }
+
+    /// FAA domestic NOTAM `number` (`MM/NNN`) of `accountability` (e.g. `JFK`, `FDC`).
+    pub fn get_faa(&self, accountability: &str, number: &str) -> Option<&NotamNormalized> {
+        self.notams.get(&NotamKey::Faa(accountability.to_ascii_uppercase(), number.to_string()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.notams.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notams.is_empty()
+    }
+
+    /// All stored NOTAMs ordered by start time and identifier.
+    pub fn iter(&self) -> impl Iterator<Item = &NotamNormalized> {
+        let mut notams: Vec<&NotamNormalized> = self.notams.values().collect();
+        notams.sort_by_key(|notam| sort_key(notam));
+        notams.into_iter()
+    }
+
+    /// NOTAMs whose A) lists `location` and that are in force at `time`, including
+    /// their D) schedule.
+    pub fn active_at(&self, location: &str, time: &NotamDateTime) -> Vec<&NotamNormalized> {
+        self.iter()
+            .filter(|notam| {
+                notam
+                    .a
+                    .as_deref()
+                    .is_some_and(|a| a.split_whitespace().any(|item| item.eq_ignore_ascii_case(location)))
+            })
+            .filter(|notam| in_force(notam, time))
+            .collect()
+    }
+
+    /// Removes NOTAMs that ended at or before `now` and returns how many were removed.
+    /// Estimated (`EST`) and permanent NOTAMs are kept.
+    pub fn purge_expired(&mut self, now: &NotamDateTime) -> usize {
+        let before = self.notams.len();
+        self.notams.retain(|_, notam| match &notam.period {
+            Some(period) if !period.estimated => period.end.is_none_or(|end| end > *now),
+            _ => true,
+        });
+        before - self.notams.len()
+    }
+}
+
+/// Nationality letters of the Q) FIR, or of the first A) location: one letter for
+/// Canada, the United States, Australia and China (`C`, `K`, `Y`, `Z`), two otherwise.
+fn issuing_region(notam: &NotamNormalized) -> String {
+    let location = notam
+        .qualifiers
+        .as_ref()
+        .map(|q| q.fir.as_str())
+        .or_else(|| notam.a.as_deref().and_then(|a| a.split_whitespace().next()))
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    let letters = match location.as_bytes().first() {
+        Some(b'C' | b'K' | b'Y') => 1,
+        Some(b'Z') if !location.starts_with("ZK") && !location.starts_with("ZM") => 1,
+        _ => 2,
+    };
+    location.chars().take(letters).collect()
+}
+
+fn in_force(notam: &NotamNormalized, time: &NotamDateTime) -> bool {
+    match &notam.period {
+        Some(period) if period.estimated && period.end.is_some_and(|end| end <= *time) => {
+            let mut open = notam.clone();
+            if let Some(period) = open.period.as_mut() {
+                period.end = None;
+            }
+            is_active_at(&open, time)
+        }
+        _ => is_active_at(notam, time),
+    }
+}
+
+fn sort_key(notam: &NotamNormalized) -> (Option<NotamDateTime>, String) {
+    let id = match (&notam.header, &notam.faa_header) {
+        (Some(header), _) => header.id.to_string(),
+        (None, Some(faa)) => format!("{} {}", faa.accountability, faa.number),
+        (None, None) => String::new(),
+    };
+    (notam.period.as_ref().map(|period| period.start), id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u8, hour: u8) -> NotamDateTime {
+        NotamDateTime::new(2024, 2, day, hour, 0).unwrap()
+    }
+
+    fn ids(notams: &[&NotamNormalized]) -> Vec<String> {
+        notams
+            .iter()
+            .filter_map(|notam| notam.header.as_ref().map(|header| header.id.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn applies_replacement_and_cancellation_chain() {
+        let mut store = NotamStore::new();
+        assert!(store
+            .ingest_raw("A0100/24 NOTAMN Q) ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005 A) ZBAA B) 2402010000 C) 2402100000 E) RWY 01/19 CLSD")
+            .is_empty());
+        assert!(store
+            .ingest_raw("A0101/24 NOTAMN Q) ZBPE/QMXLC/IV/BO/A/000/999/4004N11635E005 A) ZBAA B) 2402010000 C) 2402030000 E) TWY A CLSD")
+            .is_empty());
+        assert_eq!(ids(&store.active_at("ZBAA", &at(2, 12))), vec!["A0100/24", "A0101/24"]);
+
+        store.ingest_raw("A0110/24 NOTAMR A0100/24 Q) ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005 A) ZBAA B) 2402050000 C) 2402060000 EST E) RWY 01/19 CLSD");
+        assert_eq!(ids(&store.active_at("ZBAA", &at(2, 12))), vec!["A0101/24"]);
+        assert_eq!(ids(&store.active_at("ZBAA", &at(7, 0))), vec!["A0110/24"]);
+
+        store.ingest_raw("A0120/24 NOTAMC A0110/24 Q) ZBPE/QMRXX/IV/NBO/A/000/999/4004N11635E005 A) ZBAA B) 2402070100 E) RWY 01/19 AVBL");
+        assert!(store.active_at("ZBAA", &at(7, 2)).is_empty());
+
+        let late = store.ingest_raw("A0100/24 NOTAMN Q) ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005 A) ZBAA B) 2402010000 C) 2402100000 E) RWY 01/19 CLSD");
+        assert_eq!(late[0].code, "notam_superseded");
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn purges_expired_and_reports_unknown_references() {
+        let mut store = NotamStore::new();
+        store.ingest_raw("B0001/24 NOTAMN Q) EGTT/QMRLC/IV/NBO/A/000/999/5129N00028W005 A) EGLL B) 2402010000 C) 2402020000 E) RWY 09L CLSD");
+        store.ingest_raw("B0002/24 NOTAMN Q) EGTT/QOBCE/IV/M/A/000/010/5129N00028W005 A) EGLL B) 2402010000 C) PERM E) CRANE ERECTED");
+        let issues = store.ingest_raw("B0003/24 NOTAMC B0999/23 Q) EGTT/QMRXX/IV/NBO/A/000/999/5129N00028W005 A) EGLL B) 2402010000 E) CNL");
+        assert_eq!(issues[0].code, "notam_reference_unknown");
+
+        assert_eq!(store.purge_expired(&at(3, 0)), 1);
+        assert_eq!(ids(&store.active_at("egll", &at(3, 0))), vec!["B0002/24"]);
+
+        let issues = store.ingest_raw("B0004/24 NOTAMR Q) EGTT/QMRLC/IV/NBO/A/000/999/5129N00028W005 A) EGLL B) 2402010000 C) 2402050000 E) RWY 09R CLSD");
+        assert_eq!(issues[0].code, "notam_reference_missing");
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn keeps_identifiers_of_different_countries_apart() {
+        let mut store = NotamStore::new();
+        assert!(store
+            .ingest_raw("A1234/24 NOTAMN Q) ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005 A) ZBAA B) 2402010000 C) 2402100000 E) RWY 01/19 CLSD")
+            .is_empty());
+        assert!(store
+            .ingest_raw("A1234/24 NOTAMN Q) EGTT/QMRLC/IV/NBO/A/000/999/5129N00028W005 A) EGLL B) 2402010000 C) 2402100000 E) RWY 09L CLSD")
+            .is_empty());
+        assert_eq!(store.len(), 2);
+
+        store.ingest_raw("A1300/24 NOTAMC A1234/24 Q) EGTT/QMRXX/IV/NBO/A/000/999/5129N00028W005 A) EGLL B) 2402020000 E) RWY 09L AVBL");
+        assert_eq!(ids(&store.active_at("ZBAA", &at(3, 0))), vec!["A1234/24"]);
+        assert!(store.active_at("EGLL", &at(3, 0)).is_empty());
+        let id = crate::parse_notam_id("A1234/24").unwrap();
+        assert!(store.get("Z", &id).is_some() && store.get("EG", &id).is_none());
+    }
+
+    #[test]
+    fn keys_faa_domestic_notams_by_accountability_and_number() {
+        let mut store = NotamStore::new();
+        assert!(store.ingest_raw("!JFK 02/123 JFK RWY 4L/22R CLSD 2402010000-2402050000").is_empty());
+        assert!(store.ingest_raw("!LGA 02/123 LGA TWY B CLSD 2402010000-2402050000").is_empty());
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.active_at("JFK", &at(3, 0)).len(), 1);
+        assert!(store.get_faa("jfk", "02/123").is_some());
+
+        let issues = store.ingest_raw("!JFK 02/123 JFK RWY 4L/22R CLSD 2402010000-2402060000");
+        assert_eq!(issues[0].code, "notam_duplicate");
+        assert_eq!(issues[0].token.as_deref(), Some("JFK 02/123"));
+
+        let issues = store.ingest_raw("RWY 01/19 CLSD");
+        assert_eq!(issues[0].code, "notam_header_missing");
+        assert_eq!(store.len(), 2);
+    }
+}
@@ -0,0 +1,203 @@
+//! Consistency checks of the Q) line against A), E), F) and G).
+//!
+//! The checks are heuristics that need no aeronautical database: the first letter of
+//! an ICAO location indicator is its region, so an A) location in another region than
+//! the Q) FIR is flagged; a Q) FIR ending in `XX` is the multi-FIR form and needs more
+//! than one A) location. For runway, taxiway, apron, obstacle and common navaid and
+//! lighting subjects, E) is expected to name the subject (`QMRLC` and `RWY`).
+
+use aviation_wx_core::{issue, Dialect, Issue, NotamParsed, QScope};
+
+use crate::caac::caac_body_terms;
+use crate::lexicon::{fir_name, q_condition, q_subject};
+use crate::vertical::vertical_issues;
+
+/// Warnings for Q) lines that contradict the rest of the NOTAM, including the F)/G)
+/// checks of `vertical_issues`. Empty when there is no decoded Q) line.
+pub fn q_line_issues(parsed: &NotamParsed) -> Vec<Issue> {
+    let Some(q) = &parsed.qualifiers else {
+        return Vec::new();
+    };
+    let mut issues = Vec::new();
+
+    let known_subject = matches!(q.subject.as_str(), "XX" | "KK") || q_subject(&q.subject).is_some();
+    let known_condition = matches!(q.condition.as_str(), "XX" | "KK") || q_condition(&q.condition).is_some();
+    if !known_subject || !known_condition {
+        issues.push(issue(
+            "notam_qcode_unknown",
+            format!("Q-code {} is not in the ICAO Q-code lexicon.", q.code),
+            Some(q.code.clone()),
+        ));
+    }
+
+    let locations: Vec<String> = parsed
+        .a
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|item| item.to_ascii_uppercase())
+        .collect();
+    let multi_fir = q.fir.ends_with("XX");
+    if multi_fir && locations.len() < 2 {
+        issues.push(issue(
+            "notam_fir_mismatch",
+            format!("Q) FIR {} is for several FIRs but A) has {} location(s).", q.fir, locations.len()),
+            Some(q.fir.clone()),
+        ));
+    }
+    for location in &locations {
+        let other_region = location.chars().next() != q.fir.chars().next();
+        let other_fir = !multi_fir && location != &q.fir && fir_name(location).is_some();
+        if other_region || other_fir {
+            issues.push(issue(
+                "notam_fir_mismatch",
+                format!("A) location {} is not in Q) FIR {}.", location, q.fir),
+                Some(location.clone()),
+            ));
+        }
+    }
+
+    let words = subject_words(&q.subject);
+    if let (Some(e), Some(expected)) = (parsed.e.as_deref(), words.first()) {
+        let text = match parsed.dialect {
+            Dialect::Caac => caac_body_terms(e),
+            _ => e.to_string(),
+        }
+        .to_ascii_uppercase();
+        let mentioned = text
+            .split(|ch: char| !ch.is_ascii_alphanumeric())
+            .any(|token| words.iter().any(|word| token.starts_with(word)));
+        if !mentioned {
+            issues.push(issue(
+                "notam_subject_mismatch",
+                format!("Q-code {} subject {} is not named in E) (expected {}).", q.code, q.subject, expected),
+                Some(q.code.clone()),
+            ));
+        }
+    }
+
+    if q.scope == [QScope::Aerodrome] {
+        if locations.len() > 1 {
+            issues.push(issue(
+                "notam_scope_mismatch",
+                format!("Q) scope A (aerodrome) but A) lists {} locations.", locations.len()),
+                parsed.a.clone(),
+            ));
+        } else if let Some(location) = locations.first().filter(|location| fir_name(location).is_some()) {
+            issues.push(issue(
+                "notam_scope_mismatch",
+                format!("Q) scope A (aerodrome) but A) {} is an FIR.", location),
+                Some(location.clone()),
+            ));
+        }
+    }
+
+    if q.scope.contains(&QScope::NavigationWarning) && (parsed.f.is_none() || parsed.g.is_none()) {
+        issues.push(issue(
+            "notam_vertical_limits_missing",
+            "Navigation warning without F) and G) limits.",
+            None,
+        ));
+    }
+
+    issues.extend(vertical_issues(parsed));
+    issues
+}
+
+/// E) words naming a Q-code subject, the usual abbreviation first; empty for subjects
+/// that are not checked.
+fn subject_words(subject: &str) -> &'static [&'static str] {
+    match subject {
+        "MR" => &["RWY", "RUNWAY"],
+        "MX" => &["TWY", "TAXIWAY"],
+        "MN" => &["APRON", "APN", "STAND"],
+        "OB" => &[
+            "OBST", "CRANE", "TOWER", "MAST", "ANTENNA", "CHIMNEY", "STACK", "BLDG", "BUILDING", "TURBINE", "POLE",
+            "TREE", "RIG",
+        ],
+        "IC" => &["ILS"],
+        "IL" | "IN" => &["LOC", "LLZ", "ILS"],
+        "IG" => &["GP", "GS", "ILS"],
+        "ID" | "ND" => &["DME"],
+        "NV" => &["VOR"],
+        "NM" => &["VOR", "DME"],
+        "NB" => &["NDB"],
+        "NN" => &["TACAN"],
+        "NT" => &["VORTAC"],
+        "LP" => &["PAPI"],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_notam;
+
+    fn codes(raw: &str) -> Vec<String> {
+        q_line_issues(&parse_notam(raw).0)
+            .into_iter()
+            .map(|item| item.code)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_consistent_notams() {
+        assert!(codes(
+            "A1234/24 NOTAMN Q) ZSHA/QMRLC/IV/NBO/A/000/999/3112N12130E005 A) ZSPD B) 2402010000 C) 2402012359 \
+             E) RWY 17L/35R CLSD"
+        )
+        .is_empty());
+        assert!(codes(
+            "A0200/24 NOTAMN Q) ZXXX/QWELW/IV/BO/W/000/120/3500N11500E200 A) ZBPE ZHWH B) 2402010000 \
+             C) 2402010600 E) ROCKET LAUNCH F) SFC G) FL120"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn reports_fir_scope_and_code_mismatches() {
+        assert_eq!(
+            codes(
+                "A1234/24 NOTAMN Q) ZSHA/QMRLC/IV/NBO/A/000/999/3112N12130E005 A) ZSPD RJTT B) 2402010000 \
+                 C) 2402012359 E) RWY CLSD"
+            ),
+            vec!["notam_fir_mismatch", "notam_scope_mismatch"]
+        );
+        assert_eq!(
+            codes(
+                "A1235/24 NOTAMN Q) ZXXX/QMRLC/IV/NBO/A/000/999/3112N12130E005 A) ZBPE B) 2402010000 \
+                 C) 2402012359 E) RWY CLSD"
+            ),
+            vec!["notam_fir_mismatch", "notam_scope_mismatch"]
+        );
+        assert_eq!(
+            codes(
+                "A0201/24 NOTAMN Q) ZBPE/QWZZZ/IV/BO/W/050/100/4004N11635E005 A) ZBPE B) 2402010000 \
+                 C) 2402010600 E) PJE F) 3000FT AMSL"
+            ),
+            vec!["notam_qcode_unknown", "notam_vertical_limits_missing", "notam_lower_limit_mismatch"]
+        );
+    }
+
+    #[test]
+    fn reports_subjects_not_named_in_e() {
+        assert_eq!(
+            codes(
+                "A1236/24 NOTAMN Q) ZSHA/QMRLC/IV/NBO/A/000/999/3112N12130E005 A) ZSPD B) 2402010000 \
+                 C) 2402012359 E) TWY B CLSD"
+            ),
+            vec!["notam_subject_mismatch"]
+        );
+        assert!(codes(
+            "A1237/24 NOTAMN Q) ZBPE/QNMAS/IV/BO/E/000/999/4004N11635E025 A) ZBPE B) 2402010000 \
+             C) 2402020000 E) VOR/DME 'PEK' U/S"
+        )
+        .is_empty());
+        assert!(codes(
+            "（C0123/24 NOTAMN\nＱ）ZBPE/QMRLC/IV/NBO/A/000/999/4004N11635E005\n\
+             Ａ）ZBAA Ｂ）2402010000 Ｃ）2402100000\nＥ）跑道01/19关闭。）"
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! NOTAM F) and G) vertical limits: `SFC`, `GND`, `FL120`, `3000FT AMSL`, `500M AGL`,
+//! `UNL`, and their consistency with the Q) line lower/upper flight levels.
+
+use aviation_wx_core::{issue, Issue, NotamParsed, VerticalDatum, VerticalLimit, VerticalUnit};
+
+const FEET_PER_METER: f64 = 3.28084;
+
+/// Parses an F) or G) limit. A height without a datum is taken as AMSL.
+pub fn parse_vertical_limit(raw: &str) -> Option<VerticalLimit> {
+    let tokens: Vec<String> = raw.split_whitespace().map(|token| token.to_ascii_uppercase()).collect();
+    let limit = |datum, value, unit, feet| VerticalLimit {
+        datum,
+        value,
+        unit,
+        feet,
+        raw: raw.trim().to_string(),
+    };
+    match tokens.join(" ").as_str() {
+        "SFC" => return Some(limit(VerticalDatum::Surface, None, None, Some(0))),
+        "GND" => return Some(limit(VerticalDatum::Ground, None, None, Some(0))),
+        "UNL" | "UNLTD" | "UNLIMITED" => return Some(limit(VerticalDatum::Unlimited, None, None, None)),
+        _ => {}
+    }
+
+    let compact = tokens.concat();
+    if let Some(level) = compact.strip_prefix("FL") {
+        let level = parse_number(level, 3)?;
+        return Some(limit(VerticalDatum::FlightLevel, Some(level), Some(VerticalUnit::Fl), Some(level * 100)));
+    }
+
+    let (datum, height) = match tokens.split_last()? {
+        (last, rest) if last == "AMSL" || last == "MSL" => (VerticalDatum::Amsl, rest.concat()),
+        (last, rest) if last == "AGL" => (VerticalDatum::Agl, rest.concat()),
+        _ => (VerticalDatum::Amsl, compact),
+    };
+    let (value, unit) = if let Some(value) = height.strip_suffix("FT") {
+        (parse_number(value, 5)?, VerticalUnit::Ft)
+    } else if let Some(value) = height.strip_suffix('M') {
+        (parse_number(value, 5)?, VerticalUnit::M)
+    } else {
+        return None;
+    };
+    let feet = match unit {
+        VerticalUnit::M => (f64::from(value) * FEET_PER_METER).round() as u32,
+        _ => value,
+    };
+    Some(limit(datum, Some(value), Some(unit), Some(feet)))
+}
+
+fn parse_number(text: &str, max_digits: usize) -> Option<u32> {
+    if text.is_empty() || text.len() > max_digits || !text.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Warnings for F)/G) limits outside the Q) line levels. The Q) lower level must not be
+/// above F) and the upper level must not be below G); AMSL altitudes and flight levels
+/// are compared in feet without a pressure correction, and an AGL lower limit is not
+/// compared because the ground elevation is unknown.
+pub fn vertical_issues(parsed: &NotamParsed) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let Some(q) = &parsed.qualifiers else {
+        return issues;
+    };
+    if let (Some(limit), Some(level)) = (&parsed.lower_limit, q.lower_fl) {
+        let q_feet = u32::from(level) * 100;
+        if limit.datum != VerticalDatum::Agl && limit.feet.is_some_and(|feet| q_feet > feet) {
+            issues.push(issue(
+                "notam_lower_limit_mismatch",
+                format!("Q) lower level {:03} is above F) {}.", level, limit.raw),
+                parsed.f.clone(),
+            ));
+        }
+    }
+    if let (Some(limit), Some(level)) = (&parsed.upper_limit, q.upper_fl) {
+        let below = match limit.feet {
+            Some(feet) => u32::from(level) * 100 < feet,
+            None => level != 999,
+        };
+        if below {
+            issues.push(issue(
+                "notam_upper_limit_mismatch",
+                format!("Q) upper level {:03} is below G) {}.", level, limit.raw),
+                parsed.g.clone(),
+            ));
+        }
+    }
+    issues
+}
+
+/// Chinese description of a limit, e.g. `平均海平面 3000 英尺`.
+pub(crate) fn describe_vertical_limit(limit: &VerticalLimit) -> String {
+    let value = match (limit.value, limit.unit) {
+        (Some(value), Some(VerticalUnit::Fl)) => return format!("FL{:03}", value),
+        (Some(value), Some(VerticalUnit::M)) => format!("{} 米（约 {} 英尺）", value, limit.feet.unwrap_or_default()),
+        (Some(value), _) => format!("{} 英尺", value),
+        (None, _) => String::new(),
+    };
+    match limit.datum {
+        VerticalDatum::Surface => "地表".to_string(),
+        VerticalDatum::Ground => "地面".to_string(),
+        VerticalDatum::Unlimited => "无上限".to_string(),
+        VerticalDatum::Amsl => format!("平均海平面 {}", value),
+        VerticalDatum::Agl => format!("距地面 {}", value),
+        VerticalDatum::FlightLevel => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_notam;
+
+    #[test]
+    fn parses_limit_forms() {
+        let feet = |raw: &str| parse_vertical_limit(raw).map(|limit| (limit.datum, limit.feet));
+        assert_eq!(feet("SFC"), Some((VerticalDatum::Surface, Some(0))));
+        assert_eq!(feet("gnd"), Some((VerticalDatum::Ground, Some(0))));
+        assert_eq!(feet("FL120"), Some((VerticalDatum::FlightLevel, Some(12000))));
+        assert_eq!(feet("FL 95"), Some((VerticalDatum::FlightLevel, Some(9500))));
+        assert_eq!(feet("3000FT AMSL"), Some((VerticalDatum::Amsl, Some(3000))));
+        assert_eq!(feet("3000 FT"), Some((VerticalDatum::Amsl, Some(3000))));
+        assert_eq!(feet("500M AGL"), Some((VerticalDatum::Agl, Some(1640))));
+        assert_eq!(feet("UNL"), Some((VerticalDatum::Unlimited, None)));
+        assert_eq!(feet("ABOVE CLOUD"), None);
+
+        let limit = parse_vertical_limit("500M AGL").unwrap();
+        assert_eq!((limit.value, limit.unit), (Some(500), Some(VerticalUnit::M)));
+    }
+
+    #[test]
+    fn cross_checks_q_line_levels() {
+        let (parsed, warnings) = parse_notam(
+            "A0200/24 NOTAMN Q) ZBPE/QWPLW/IV/BO/W/000/120/4004N11635E005 A) ZBPE B) 2402010000 C) 2402010600 \
+             E) PJE F) SFC G) FL120",
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert!(vertical_issues(&parsed).is_empty());
+
+        let (parsed, _) = parse_notam(
+            "A0201/24 NOTAMN Q) ZBPE/QWPLW/IV/BO/W/050/100/4004N11635E005 A) ZBPE B) 2402010000 C) 2402010600 \
+             E) PJE F) 3000FT AMSL G) UNL",
+        );
+        let issues = vertical_issues(&parsed);
+        let codes: Vec<&str> = issues.iter().map(|item| item.code.as_str()).collect();
+        assert_eq!(codes, vec!["notam_lower_limit_mismatch", "notam_upper_limit_mismatch"]);
+
+        let (parsed, warnings) = parse_notam("A0202/24 NOTAMN A) ZBPE B) 2402010000 E) PJE F) ABOVE CLOUD");
+        assert!(parsed.lower_limit.is_none());
+        assert_eq!(warnings, vec!["Unrecognized token: ABOVE CLOUD".to_string()]);
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::PathBuf;
+
+use aviation_wx_notam::{normalize_notam, notam_to_aixm, parse_notam};
+
+/// Optional schema elements the fixtures carry and the exporter does not write: the
+/// issue time, Digital NOTAM scenario fields, feature lifetime and operation sequence.
+const NOT_EXPORTED: &[&str] = &[
+    "event:issued",
+    "event:encoding",
+    "event:scenario",
+    "event:version",
+    "aixm:featureLifetime",
+    "aixm:operationSequence",
+];
+
+/// Each element of a document in order, as its path with its attribute names (`@name`)
+/// and child element names in document order, repeated children listed once. Elements
+/// in `skip` are left out with their content; panics when the tags are not balanced.
+fn shape(xml: &str, skip: &[&str]) -> Vec<(String, Vec<String>)> {
+    let mut elements: Vec<(String, Vec<String>)> = Vec::new();
+    // Open elements as name and index in `elements`, `None` inside a skipped element.
+    let mut stack: Vec<(String, Option<usize>)> = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = &comment[comment.find("-->").expect("closed comment") + 3..];
+            continue;
+        }
+        let end = rest.find('>').expect("closed tag");
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('?') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(stack.pop().map(|(open, _)| open).as_deref(), Some(name), "unbalanced tag");
+            continue;
+        }
+        let self_closing = tag.ends_with('/');
+        let mut parts = tag.trim_end_matches('/').split_whitespace();
+        let name = parts.next().expect("tag name");
+        let parent = stack.last().map(|(_, index)| *index);
+        let index = match parent {
+            Some(None) => None,
+            _ if skip.contains(&name) => None,
+            _ => {
+                let path = stack.iter().map(|(open, _)| open.as_str()).chain([name]).collect::<Vec<_>>().join("/");
+                if let Some(Some(parent)) = parent {
+                    let children = &mut elements[parent].1;
+                    if children.last().map(String::as_str) != Some(name) {
+                        children.push(name.to_string());
+                    }
+                }
+                let attributes = parts
+                    .filter_map(|part| part.split_once('='))
+                    .map(|(attribute, _)| format!("@{}", attribute))
+                    .collect();
+                elements.push((path, attributes));
+                Some(elements.len() - 1)
+            }
+        };
+        if !self_closing {
+            stack.push((name.to_string(), index));
+        }
+    }
+    assert!(stack.is_empty(), "unclosed tags: {:?}", stack);
+    elements
+}
+
+#[test]
+fn exports_follow_the_element_order_of_the_schema_fixtures() {
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/aixm");
+    let mut fixtures: Vec<_> = fs::read_dir(base).expect("aixm fixtures").filter_map(|entry| entry.ok()).collect();
+    fixtures.sort_by_key(|entry| entry.file_name());
+    assert!(!fixtures.is_empty());
+
+    for entry in fixtures {
+        let fixture = fs::read_to_string(entry.path()).expect("fixture");
+        let raw = fixture
+            .split_once("<!-- NOTAM: ")
+            .and_then(|(_, rest)| rest.split_once(" -->"))
+            .map(|(raw, _)| raw)
+            .expect("fixture names its NOTAM");
+        let exported = shape(&notam_to_aixm(&normalize_notam(&parse_notam(raw).0)), &[]);
+        let expected = shape(&fixture, NOT_EXPORTED);
+        assert_eq!(exported.len(), expected.len(), "{:?}", entry.file_name());
+        for (exported, expected) in exported.iter().zip(&expected) {
+            assert_eq!(exported, expected, "{:?}", entry.file_name());
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Semantic comparison of two TAFs for the same station, typically a `TAF AMD`
+//! against the forecast it replaces.
+//!
+//! Both TAFs are expanded into hourly timelines and compared hour by hour over the
+//! validity of the newer one. Consecutive hours with the same differences are merged
+//! into one period. Alternatives are compared by kind, probability and conditions, so
+//! a `TEMPO` whose window moves only shows up in the hours it was added or removed.
+
+use aviation_wx_core::{describe_clouds, describe_visibility_meters, describe_wind, TafNormalized, UtcTime, ValidityPeriod};
+use serde::{Deserialize, Serialize};
+
+use crate::describe_trend_kind;
+use crate::timeline::{hourly_timeline, TafAlternative, TafConditions, TafTimelinePeriod};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TafElement {
+    Wind,
+    Visibility,
+    Weather,
+    Clouds,
+    Alternatives,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TafPeriodChange {
+    pub start: UtcTime,
+    pub end: UtcTime,
+    pub elements: Vec<TafElement>,
+    /// Prevailing conditions in the previous TAF, `None` when it did not cover the period.
+    pub before: Option<TafConditions>,
+    pub after: TafConditions,
+    pub alternatives_before: Vec<TafAlternative>,
+    pub alternatives_after: Vec<TafAlternative>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TafDiff {
+    pub station: Option<String>,
+    pub validity_before: Option<ValidityPeriod>,
+    pub validity_after: Option<ValidityPeriod>,
+    pub changes: Vec<TafPeriodChange>,
+}
+
+impl TafDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.validity_before == self.validity_after
+    }
+}
+
+/// Lists the periods of `current` whose forecast differs from `previous`.
+pub fn diff_taf(previous: &TafNormalized, current: &TafNormalized) -> TafDiff {
+    let before = hourly_timeline(previous);
+    let mut changes: Vec<TafPeriodChange> = Vec::new();
+
+    for slot in hourly_timeline(current) {
+        let old = before
+            .iter()
+            .find(|candidate| (candidate.start.day, candidate.start.hour) == (slot.start.day, slot.start.hour));
+        let elements = changed_elements(old, &slot);
+        if elements.is_empty() {
+            continue;
+        }
+        let change = TafPeriodChange {
+            start: slot.start,
+            end: slot.end,
+            elements,
+            before: old.map(|period| period.prevailing.clone()),
+            after: slot.prevailing,
+            alternatives_before: old.map(|period| period.alternatives.clone()).unwrap_or_default(),
+            alternatives_after: slot.alternatives,
+        };
+        match changes.last_mut() {
+            Some(last) if last.end == change.start && same_change(last, &change) => last.end = change.end,
+            _ => changes.push(change),
+        }
+    }
+
+    TafDiff {
+        station: current.station.clone().or_else(|| previous.station.clone()),
+        validity_before: previous.validity.clone(),
+        validity_after: current.validity.clone(),
+        changes,
+    }
+}
+
+fn changed_elements(old: Option<&TafTimelinePeriod>, new: &TafTimelinePeriod) -> Vec<TafElement> {
+    let Some(old) = old else {
+        return vec![TafElement::Wind, TafElement::Visibility, TafElement::Weather, TafElement::Clouds];
+    };
+    let mut elements = Vec::new();
+    if old.prevailing.wind != new.prevailing.wind {
+        elements.push(TafElement::Wind);
+    }
+    if old.prevailing.visibility_m != new.prevailing.visibility_m {
+        elements.push(TafElement::Visibility);
+    }
+    if old.prevailing.weather != new.prevailing.weather {
+        elements.push(TafElement::Weather);
+    }
+    if old.prevailing.clouds != new.prevailing.clouds {
+        elements.push(TafElement::Clouds);
+    }
+    if alternative_keys(&old.alternatives) != alternative_keys(&new.alternatives) {
+        elements.push(TafElement::Alternatives);
+    }
+    elements
+}
+
+fn alternative_keys(alternatives: &[TafAlternative]) -> Vec<(String, &TafConditions)> {
+    alternatives
+        .iter()
+        .map(|alt| (describe_trend_kind(alt.kind, alt.probability), &alt.conditions))
+        .collect()
+}
+
+fn same_change(a: &TafPeriodChange, b: &TafPeriodChange) -> bool {
+    a.elements == b.elements
+        && a.before == b.before
+        && a.after == b.after
+        && alternative_keys(&a.alternatives_before) == alternative_keys(&b.alternatives_before)
+        && alternative_keys(&a.alternatives_after) == alternative_keys(&b.alternatives_after)
+}
+
+/// Human-readable summary of `diff` for dispatchers.
+pub fn describe_taf_diff(diff: &TafDiff, lang: &str) -> String {
+    if lang != "zh-CN" {
+        return "Translation only supports zh-CN for now.".to_string();
+    }
+
+    let mut parts = Vec::new();
+    if let Some(station) = &diff.station {
+        parts.push(format!("台站 {}", station));
+    }
+    if diff.validity_before != diff.validity_after {
+        parts.push(format!(
+            "有效期由 {} 改为 {}",
+            describe_validity(diff.validity_before.as_ref()),
+            describe_validity(diff.validity_after.as_ref())
+        ));
+    }
+    if diff.changes.is_empty() {
+        parts.push("预报要素无变化".to_string());
+        return parts.join("，");
+    }
+
+    let mut text = parts.join("，");
+    let lines: Vec<String> = diff.changes.iter().map(describe_change).collect();
+    if !text.is_empty() {
+        text.push('。');
+    }
+    text.push_str("变化：");
+    text.push_str(&lines.join("；"));
+    text
+}
+
+fn describe_change(change: &TafPeriodChange) -> String {
+    let mut line = vec![format!(
+        "{:02}日{:02}Z-{:02}日{:02}Z",
+        change.start.day, change.start.hour, change.end.day, change.end.hour
+    )];
+    let Some(before) = &change.before else {
+        line.push(format!("新增预报 {}", describe_conditions(&change.after)));
+        return line.join("，");
+    };
+    for element in &change.elements {
+        let text = match element {
+            TafElement::Wind => format!(
+                "风 {} → {}",
+                before.wind.as_ref().map(describe_wind).unwrap_or_else(|| "无".to_string()),
+                change.after.wind.as_ref().map(describe_wind).unwrap_or_else(|| "无".to_string())
+            ),
+            TafElement::Visibility => format!(
+                "能见度 {} → {}",
+                describe_visibility(before.visibility_m),
+                describe_visibility(change.after.visibility_m)
+            ),
+            TafElement::Weather => format!(
+                "天气 {} → {}",
+                describe_weather(&before.weather),
+                describe_weather(&change.after.weather)
+            ),
+            TafElement::Clouds => format!(
+                "云 {} → {}",
+                describe_clouds(&before.clouds),
+                describe_clouds(&change.after.clouds)
+            ),
+            TafElement::Alternatives => format!(
+                "备选 {} → {}",
+                describe_alternatives(&change.alternatives_before),
+                describe_alternatives(&change.alternatives_after)
+            ),
+        };
+        line.push(text);
+    }
+    line.join("，")
+}
+
+fn describe_conditions(conditions: &TafConditions) -> String {
+    let mut parts = Vec::new();
+    if let Some(wind) = &conditions.wind {
+        parts.push(format!("风 {}", describe_wind(wind)));
+    }
+    parts.push(format!("能见度 {}", describe_visibility(conditions.visibility_m)));
+    parts.push(format!("天气 {}", describe_weather(&conditions.weather)));
+    parts.push(format!("云 {}", describe_clouds(&conditions.clouds)));
+    parts.join(" ")
+}
+
+fn describe_alternatives(alternatives: &[TafAlternative]) -> String {
+    if alternatives.is_empty() {
+        return "无".to_string();
+    }
+    alternatives
+        .iter()
+        .map(|alt| {
+            format!(
+                "{}（{}）",
+                describe_trend_kind(alt.kind, alt.probability),
+                describe_conditions(&alt.conditions)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("、")
+}
+
+fn describe_visibility(visibility_m: Option<u32>) -> String {
+    visibility_m
+        .map(describe_visibility_meters)
+        .unwrap_or_else(|| "未报".to_string())
+}
+
+fn describe_weather(weather: &[String]) -> String {
+    if weather.is_empty() {
+        "无".to_string()
+    } else {
+        weather.join(" ")
+    }
+}
+
+fn describe_validity(validity: Option<&ValidityPeriod>) -> String {
+    match validity {
+        Some(period) => format!(
+            "{:02}日{:02}Z-{:02}日{:02}Z",
+            period.from.day, period.from.hour, period.to.day, period.to.hour
+        ),
+        None => "未报".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_taf, parse_taf};
+
+    fn taf(raw: &str) -> TafNormalized {
+        normalize_taf(&parse_taf(raw).0)
+    }
+
+    #[test]
+    fn identical_tafs_have_no_changes() {
+        let raw = "TAF EGLL 011110Z 0112/0218 24010KT 8000 BKN012 TEMPO 0112/0116 3000 RA";
+        let diff = diff_taf(&taf(raw), &taf(raw));
+        assert!(diff.is_empty());
+        assert_eq!(describe_taf_diff(&diff, "zh-CN"), "台站 EGLL，预报要素无变化");
+    }
+
+    #[test]
+    fn amendment_changes_are_grouped_by_period() {
+        let previous = taf("TAF EGLL 011110Z 0112/0218 24010KT 8000 BKN012 TEMPO 0112/0116 3000 RA");
+        let amended = taf(
+            "TAF AMD EGLL 011230Z 0112/0218 24010KT 8000 BKN012 TEMPO 0112/0116 3000 RA \
+             FM011800 30015KT 9999 SCT030",
+        );
+        let diff = diff_taf(&previous, &amended);
+        assert_eq!(diff.changes.len(), 1);
+        let change = &diff.changes[0];
+        assert_eq!((change.start.day, change.start.hour), (1, 18));
+        assert_eq!((change.end.day, change.end.hour), (2, 18));
+        assert_eq!(
+            change.elements,
+            vec![TafElement::Wind, TafElement::Visibility, TafElement::Clouds]
+        );
+        let summary = describe_taf_diff(&diff, "zh-CN");
+        assert!(summary.contains("01日18Z-02日18Z"), "{}", summary);
+        assert!(summary.contains("风 240° 10kt → 300° 15kt"), "{}", summary);
+    }
+
+    #[test]
+    fn moved_tempo_only_reports_hours_that_differ() {
+        let previous = taf("TAF EGLL 011110Z 0112/0218 24010KT 8000 BKN012 TEMPO 0112/0116 3000 RA");
+        let amended = taf("TAF EGLL 011230Z 0112/0218 24010KT 8000 BKN012 TEMPO 0114/0118 3000 RA");
+        let diff = diff_taf(&previous, &amended);
+        let spans: Vec<(u8, u8)> = diff
+            .changes
+            .iter()
+            .map(|change| (change.start.hour, change.end.hour))
+            .collect();
+        assert_eq!(spans, vec![(12, 14), (16, 18)]);
+        assert!(diff.changes.iter().all(|change| change.elements == vec![TafElement::Alternatives]));
+    }
+}
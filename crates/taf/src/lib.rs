@@ -1,11 +1,25 @@
-﻿use aviation_wx_core::{finalize_issues, issue_from_legacy, parse, CloudLayer, DecodeResponse, DetailLevel, MessageType, TafNormalized, TafParsed, TafTrend, TafTrendNormalized, Visibility};
+﻿use aviation_wx_core::{finalize_issues, issue, issue_from_legacy, parse, CloudLayer, DecodeResponse, DetailLevel, Dialect, Issue, LayerForecast, MessageType, Pressure, TafNormalized, TafParsed, TafTemperatureKind, TafTrend, TafTrendKind, TafTrendNormalized, Visibility, WindShear, WindShearNormalized};
 use regex::Regex;
 
+pub mod diff;
+pub mod timeline;
+pub mod validate;
+pub mod verify;
+
+pub use diff::{describe_taf_diff, diff_taf, TafDiff, TafElement, TafPeriodChange};
+pub use timeline::{conditions_at, expand_timeline, hourly_timeline, TafAlternative, TafConditions, TafTimelinePeriod};
+pub use validate::{validate_taf, TafValidation};
+pub use verify::{verify_taf, CategoryScore, OccurrenceScore, TafVerification, TafVerificationEntry, TafVerificationSummary};
+
 pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
+    parse_taf_with_dialect(raw, None)
+}
+
+/// Parses a TAF using `dialect`, or the dialect inferred from the station when `None`.
+pub fn parse_taf_with_dialect(raw: &str, dialect: Option<Dialect>) -> (TafParsed, Vec<String>) {
     let mut warnings = Vec::new();
     let tokens: Vec<String> = raw
-        .replace('\n', " ")
-        .replace('\r', " ")
+        .replace(['\n', '\r'], " ")
         .split_whitespace()
         .map(|s| s.trim().to_string())
         .collect();
@@ -17,12 +31,17 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
     let mut visibility = None;
     let mut weather = Vec::new();
     let mut clouds = Vec::new();
+    let mut icing = Vec::new();
+    let mut turbulence = Vec::new();
+    let mut wind_shear = None;
+    let mut qnh = None;
     let mut temperatures = Vec::new();
     let mut trends: Vec<TafTrend> = Vec::new();
+    let mut rmk_raw = None;
     let mut raw_tokens: Vec<String> = Vec::new();
 
     let mut idx = 0;
-    if tokens.get(0).map(|t| t.as_str()) == Some("TAF") {
+    if tokens.first().map(|t| t.as_str()) == Some("TAF") {
         idx += 1;
         if let Some(token) = tokens.get(idx) {
             if token == "AMD" || token == "COR" || token == "RTD" {
@@ -54,26 +73,26 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
         }
     }
 
+    let dialect = dialect
+        .or_else(|| station.as_deref().map(Dialect::from_station))
+        .unwrap_or_default();
+
     let weather_re = Regex::new(r"^(\+|\-|VC)?[A-Z]{2,6}$").unwrap();
 
     let mut current_trend: Option<TafTrend> = None;
 
     while idx < tokens.len() {
         let token = tokens[idx].clone();
-        if token == "BECMG" || token == "TEMPO" {
-            if let Some(trend) = current_trend.take() {
-                trends.push(trend);
+        if let Some((trend, consumed)) = parse_change_indicator(&tokens[idx..]) {
+            if let Some(previous) = current_trend.take() {
+                trends.push(previous);
+            }
+            let has_period = trend.kind != TafTrendKind::Fm;
+            current_trend = Some(trend);
+            idx += consumed;
+            if !has_period {
+                continue;
             }
-            current_trend = Some(TafTrend {
-                kind: token.clone(),
-                period: None,
-                wind: None,
-                visibility: None,
-                weather: Vec::new(),
-                clouds: Vec::new(),
-                raw_tokens: Vec::new(),
-            });
-            idx += 1;
             if let Some(next) = tokens.get(idx) {
                 if parse::parse_validity(next).is_ok() {
                     let (_, period) = parse::parse_validity(next).unwrap();
@@ -91,9 +110,12 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
                 distance: 10000,
                 unit: "M".to_string(),
                 raw: token.clone(),
+                distance_m: None,
             };
             if let Some(trend) = current_trend.as_mut() {
                 trend.visibility = Some(vis);
+                trend.clears_weather = true;
+                trend.clears_clouds = true;
             } else {
                 visibility = Some(vis);
             }
@@ -101,6 +123,54 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
             continue;
         }
 
+        if token == "NSW" {
+            if let Some(trend) = current_trend.as_mut() {
+                trend.clears_weather = true;
+                idx += 1;
+                continue;
+            }
+        }
+
+        if let Ok(("", layer)) = parse::parse_layer_forecast(&token, '6') {
+            match current_trend.as_mut() {
+                Some(trend) => trend.icing.push(layer),
+                None => icing.push(layer),
+            }
+            idx += 1;
+            continue;
+        }
+
+        if let Ok(("", layer)) = parse::parse_layer_forecast(&token, '5') {
+            match current_trend.as_mut() {
+                Some(trend) => trend.turbulence.push(layer),
+                None => turbulence.push(layer),
+            }
+            idx += 1;
+            continue;
+        }
+
+        if let Ok(("", shear)) = parse::parse_wind_shear(&token) {
+            match current_trend.as_mut() {
+                Some(trend) => trend.wind_shear = Some(shear),
+                None => wind_shear = Some(shear),
+            }
+            idx += 1;
+            continue;
+        }
+
+        if let Ok(("", value)) = parse::parse_qnh_inches(&token) {
+            let pressure = Pressure {
+                value,
+                unit: "inHg*100".to_string(),
+            };
+            match current_trend.as_mut() {
+                Some(trend) => trend.qnh = Some(pressure),
+                None => qnh = Some(pressure),
+            }
+            idx += 1;
+            continue;
+        }
+
         if parse::parse_wind(&token).is_ok() {
             let (_, parsed) = parse::parse_wind(&token).unwrap();
             if let Some(trend) = current_trend.as_mut() {
@@ -123,8 +193,32 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
             continue;
         }
 
-        if parse::parse_cloud_layer(&token).is_ok() {
-            let (_, (amount, height, cloud_type)) = parse::parse_cloud_layer(&token).unwrap();
+        if dialect.uses_statute_miles() {
+            let parsed = match tokens.get(idx + 1).and_then(|next| parse::parse_visibility_sm_mixed(&token, next)) {
+                Some(mixed) => {
+                    idx += 1;
+                    Some(mixed)
+                }
+                None => parse::parse_visibility_sm(&token)
+                    .ok()
+                    .filter(|(rest, _)| rest.is_empty())
+                    .map(|(_, parsed)| parsed),
+            };
+            if let Some(parsed) = parsed {
+                if let Some(trend) = current_trend.as_mut() {
+                    trend.visibility = Some(parsed);
+                } else {
+                    visibility = Some(parsed);
+                }
+                idx += 1;
+                continue;
+            }
+        }
+
+        if parse::parse_cloud_layer(&token).is_ok() || (token == "CLR" && dialect.accepts_clr()) {
+            let (amount, height, cloud_type) = parse::parse_cloud_layer(&token)
+                .map(|(_, layer)| layer)
+                .unwrap_or_else(|_| (token.clone(), None, None));
             let layer = CloudLayer {
                 amount,
                 height_ft: height,
@@ -132,6 +226,9 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
                 raw: token.clone(),
             };
             if let Some(trend) = current_trend.as_mut() {
+                if layer.amount == "NSC" || layer.amount == "SKC" {
+                    trend.clears_clouds = true;
+                }
                 trend.clouds.push(layer);
             } else {
                 clouds.push(layer);
@@ -140,12 +237,17 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
             continue;
         }
 
-        if token.starts_with("TX") || token.starts_with("TN") {
-            temperatures.push(token.clone());
+        if let Ok(("", forecast)) = parse::parse_taf_temperature(&token) {
+            temperatures.push(forecast);
             idx += 1;
             continue;
         }
 
+        if token == "RMK" {
+            rmk_raw = Some(tokens[idx + 1..].join(" "));
+            break;
+        }
+
         if weather_re.is_match(&token) {
             if let Some(trend) = current_trend.as_mut() {
                 trend.weather.push(token.clone());
@@ -177,43 +279,201 @@ pub fn parse_taf(raw: &str) -> (TafParsed, Vec<String>) {
     (
         TafParsed {
             station,
+            dialect,
             issue_time,
             validity,
             wind,
             visibility,
             weather,
             clouds,
+            icing,
+            turbulence,
+            wind_shear,
+            qnh,
             temperatures,
             trends,
+            rmk_raw,
             raw_tokens,
         },
         warnings,
     )
 }
 
+/// Recognizes `BECMG`, `TEMPO`, `INTER`, `FMddhhmm`, `PROBnn` and `PROBnn TEMPO`/`PROBnn INTER`
+/// at the start of `tokens`, returning an empty trend and the number of indicator tokens consumed.
+fn parse_change_indicator(tokens: &[String]) -> Option<(TafTrend, usize)> {
+    let first = tokens.first()?.as_str();
+    let (kind, probability, from_time, consumed) = match first {
+        "BECMG" => (TafTrendKind::Becmg, None, None, 1),
+        "TEMPO" => (TafTrendKind::Tempo, None, None, 1),
+        "INTER" => (TafTrendKind::Inter, None, None, 1),
+        _ if first.len() == 8 && first.starts_with("FM") && first[2..].chars().all(|c| c.is_ascii_digit()) => {
+            let (_, time) = parse::parse_time_group(&first[2..]).ok()?;
+            (TafTrendKind::Fm, None, Some(time), 1)
+        }
+        _ if first.len() == 6 && first.starts_with("PROB") && first[4..].chars().all(|c| c.is_ascii_digit()) => {
+            let probability = first[4..].parse::<u8>().ok()?;
+            match tokens.get(1).map(|t| t.as_str()) {
+                Some("TEMPO") => (TafTrendKind::ProbTempo, Some(probability), None, 2),
+                Some("INTER") => (TafTrendKind::Inter, Some(probability), None, 2),
+                _ => (TafTrendKind::Prob, Some(probability), None, 1),
+            }
+        }
+        _ => return None,
+    };
+    Some((
+        TafTrend {
+            kind,
+            probability,
+            from_time,
+            period: None,
+            wind: None,
+            visibility: None,
+            weather: Vec::new(),
+            clouds: Vec::new(),
+            clears_weather: false,
+            clears_clouds: false,
+            icing: Vec::new(),
+            turbulence: Vec::new(),
+            wind_shear: None,
+            qnh: None,
+            raw_tokens: Vec::new(),
+        },
+        consumed,
+    ))
+}
+
+fn describe_trend_kind(kind: TafTrendKind, probability: Option<u8>) -> String {
+    match (kind, probability) {
+        (TafTrendKind::Prob, Some(prob)) => format!("PROB{}", prob),
+        (TafTrendKind::ProbTempo, Some(prob)) => format!("PROB{} TEMPO", prob),
+        (TafTrendKind::Inter, Some(prob)) => format!("PROB{} INTER", prob),
+        (TafTrendKind::ProbTempo, None) => "PROB TEMPO".to_string(),
+        _ => kind.as_str().to_string(),
+    }
+}
+
 pub fn normalize_taf(parsed: &TafParsed) -> TafNormalized {
     TafNormalized {
         station: parsed.station.clone(),
+        dialect: parsed.dialect,
         issue_time: parsed.issue_time.clone(),
         validity: parsed.validity.clone(),
         wind: parsed.wind.as_ref().map(aviation_wx_core::normalize_wind),
         visibility_m: parsed.visibility.as_ref().and_then(aviation_wx_core::visibility_to_meters),
         weather: parsed.weather.clone(),
         clouds: parsed.clouds.clone(),
+        icing: parsed.icing.clone(),
+        turbulence: parsed.turbulence.clone(),
+        wind_shear: parsed.wind_shear.as_ref().map(normalize_wind_shear),
+        qnh_inhg: parsed.qnh.as_ref().map(|p| aviation_wx_core::altimeter_to_inhg(p.value)),
+        qnh_hpa: parsed
+            .qnh
+            .as_ref()
+            .map(|p| aviation_wx_core::inhg_to_hpa(aviation_wx_core::altimeter_to_inhg(p.value))),
         temperatures: parsed.temperatures.clone(),
         trends: parsed
             .trends
             .iter()
             .map(|trend| TafTrendNormalized {
-                kind: trend.kind.clone(),
+                kind: trend.kind,
+                probability: trend.probability,
+                from_time: trend.from_time.clone(),
                 period: trend.period.clone(),
                 wind: trend.wind.as_ref().map(aviation_wx_core::normalize_wind),
                 visibility_m: trend.visibility.as_ref().and_then(aviation_wx_core::visibility_to_meters),
                 weather: trend.weather.clone(),
                 clouds: trend.clouds.clone(),
+                clears_weather: trend.clears_weather,
+                clears_clouds: trend.clears_clouds,
+                icing: trend.icing.clone(),
+                turbulence: trend.turbulence.clone(),
+                wind_shear: trend.wind_shear.as_ref().map(normalize_wind_shear),
+                qnh_inhg: trend.qnh.as_ref().map(|p| aviation_wx_core::altimeter_to_inhg(p.value)),
+                qnh_hpa: trend
+                    .qnh
+                    .as_ref()
+                    .map(|p| aviation_wx_core::inhg_to_hpa(aviation_wx_core::altimeter_to_inhg(p.value))),
             })
             .collect(),
+        rmk_raw: parsed.rmk_raw.clone(),
+    }
+}
+
+fn normalize_wind_shear(shear: &WindShear) -> WindShearNormalized {
+    WindShearNormalized {
+        height_ft: shear.height_ft,
+        wind: aviation_wx_core::normalize_wind(&shear.wind),
+    }
+}
+
+fn icing_description(code: u8) -> &'static str {
+    match code {
+        0 => "无或轻微",
+        1 => "轻度",
+        2 => "轻度（云中）",
+        3 => "轻度（降水中）",
+        4 => "中度",
+        5 => "中度（云中）",
+        6 => "中度（降水中）",
+        7 => "严重",
+        8 => "严重（云中）",
+        9 => "严重（降水中）",
+        _ => "未知",
+    }
+}
+
+fn turbulence_description(code: u8) -> &'static str {
+    match code {
+        0 => "无",
+        1 => "轻度",
+        2 => "中度（晴空，偶有）",
+        3 => "中度（晴空，频繁）",
+        4 => "中度（云中，偶有）",
+        5 => "中度（云中，频繁）",
+        6 => "严重（晴空，偶有）",
+        7 => "严重（晴空，频繁）",
+        8 => "严重（云中，偶有）",
+        9 => "严重（云中，频繁）",
+        _ => "未知",
+    }
+}
+
+fn describe_hazards(
+    icing: &[LayerForecast],
+    turbulence: &[LayerForecast],
+    wind_shear: Option<&WindShearNormalized>,
+    qnh_inhg: Option<f32>,
+    qnh_hpa: Option<f32>,
+) -> Vec<String> {
+    let mut parts = Vec::new();
+    for layer in icing {
+        parts.push(format!(
+            "积冰 {} {}-{}ft",
+            icing_description(layer.code),
+            layer.base_ft,
+            layer.base_ft + layer.thickness_ft
+        ));
+    }
+    for layer in turbulence {
+        parts.push(format!(
+            "颠簸 {} {}-{}ft",
+            turbulence_description(layer.code),
+            layer.base_ft,
+            layer.base_ft + layer.thickness_ft
+        ));
+    }
+    if let Some(shear) = wind_shear {
+        parts.push(format!(
+            "低空风切变 {}ft 处风 {}",
+            shear.height_ft,
+            aviation_wx_core::describe_wind(&shear.wind)
+        ));
+    }
+    if let (Some(inhg), Some(hpa)) = (qnh_inhg, qnh_hpa) {
+        parts.push(format!("最低 QNH {:.2} inHg（≈ {:.1} hPa）", inhg, hpa));
     }
+    parts
 }
 
 pub fn translate_taf(normalized: &TafNormalized, detail: DetailLevel, lang: &str) -> String {
@@ -236,7 +496,7 @@ pub fn translate_taf(normalized: &TafNormalized, detail: DetailLevel, lang: &str
     }
 
     if let Some(wind) = &normalized.wind {
-        parts.push(format!("风 {}", aviation_wx_core::describe_wind(wind)));
+        parts.push(format!("风 {}", aviation_wx_core::describe_wind_for_dialect(wind, normalized.dialect)));
     }
     if let Some(vis) = normalized.visibility_m {
         parts.push(format!("能见度 {}", aviation_wx_core::describe_visibility_meters(vis)));
@@ -247,6 +507,13 @@ pub fn translate_taf(normalized: &TafNormalized, detail: DetailLevel, lang: &str
     if !normalized.clouds.is_empty() {
         parts.push(format!("云 {}", aviation_wx_core::describe_clouds(&normalized.clouds)));
     }
+    parts.extend(describe_hazards(
+        &normalized.icing,
+        &normalized.turbulence,
+        normalized.wind_shear.as_ref(),
+        normalized.qnh_inhg,
+        normalized.qnh_hpa,
+    ));
 
     let mut text = parts.join("，");
 
@@ -254,7 +521,10 @@ pub fn translate_taf(normalized: &TafNormalized, detail: DetailLevel, lang: &str
         let mut trend_lines = Vec::new();
         for trend in &normalized.trends {
             let mut line = Vec::new();
-            line.push(format!("{}", trend.kind));
+            line.push(describe_trend_kind(trend.kind, trend.probability));
+            if let Some(from) = &trend.from_time {
+                line.push(format!("{:02}日{:02}:{:02}Z起", from.day, from.hour, from.minute));
+            }
             if let Some(period) = &trend.period {
                 line.push(format!(
                     "{:02}日{:02}Z-{:02}日{:02}Z",
@@ -262,17 +532,28 @@ pub fn translate_taf(normalized: &TafNormalized, detail: DetailLevel, lang: &str
                 ));
             }
             if let Some(wind) = &trend.wind {
-                line.push(format!("风 {}", aviation_wx_core::describe_wind(wind)));
+                line.push(format!("风 {}", aviation_wx_core::describe_wind_for_dialect(wind, normalized.dialect)));
             }
             if let Some(vis) = trend.visibility_m {
                 line.push(format!("能见度 {}", aviation_wx_core::describe_visibility_meters(vis)));
             }
             if !trend.weather.is_empty() {
                 line.push(format!("天气 {}", trend.weather.join(" ")));
+            } else if trend.clears_weather {
+                line.push("无显著天气".to_string());
             }
-            if !trend.clouds.is_empty() {
+            if trend.clears_clouds && trend.clouds.iter().all(|layer| layer.height_ft.is_none()) {
+                line.push("无显著云".to_string());
+            } else if !trend.clouds.is_empty() {
                 line.push(format!("云 {}", aviation_wx_core::describe_clouds(&trend.clouds)));
             }
+            line.extend(describe_hazards(
+                &trend.icing,
+                &trend.turbulence,
+                trend.wind_shear.as_ref(),
+                trend.qnh_inhg,
+                trend.qnh_hpa,
+            ));
             trend_lines.push(line.join("，"));
         }
         if !trend_lines.is_empty() {
@@ -282,21 +563,69 @@ pub fn translate_taf(normalized: &TafNormalized, detail: DetailLevel, lang: &str
     }
 
     if detail == DetailLevel::Full && !normalized.temperatures.is_empty() {
-        text.push_str("。温度组：");
-        text.push_str(&normalized.temperatures.join(" "));
+        let temps: Vec<String> = normalized
+            .temperatures
+            .iter()
+            .map(|item| {
+                let label = match item.kind {
+                    TafTemperatureKind::Max => "最高",
+                    TafTemperatureKind::Min => "最低",
+                };
+                format!("{} {}°C（{:02}日{:02}Z）", label, item.value_c, item.at.day, item.at.hour)
+            })
+            .collect();
+        text.push_str("。温度预报：");
+        text.push_str(&temps.join("，"));
+    }
+
+    if detail != DetailLevel::Brief {
+        if let Some(rmk) = &normalized.rmk_raw {
+            text.push_str("。备注（RMK）：");
+            text.push_str(rmk);
+        }
     }
 
     text
 }
 
+/// Flags max/min temperature groups whose time falls outside the TAF validity.
+pub fn temperature_issues(parsed: &TafParsed) -> Vec<Issue> {
+    let Some(validity) = &parsed.validity else {
+        return Vec::new();
+    };
+    parsed
+        .temperatures
+        .iter()
+        .filter(|item| !validity.contains(&item.at))
+        .map(|item| {
+            issue(
+                "temperature_outside_validity",
+                format!("Temperature group {} is outside the TAF validity period.", item.raw),
+                Some(item.raw.clone()),
+            )
+        })
+        .collect()
+}
+
 pub fn decode_taf(raw: &str, detail: DetailLevel, lang: &str) -> DecodeResponse {
-    let (parsed, warnings_legacy_raw) = parse_taf(raw);
+    decode_taf_with_dialect(raw, None, detail, lang)
+}
+
+pub fn decode_taf_with_dialect(
+    raw: &str,
+    dialect: Option<Dialect>,
+    detail: DetailLevel,
+    lang: &str,
+) -> DecodeResponse {
+    let (parsed, warnings_legacy_raw) = parse_taf_with_dialect(raw, dialect);
     let normalized = normalize_taf(&parsed);
     let explain = translate_taf(&normalized, detail, lang);
 
     let mut warnings: Vec<aviation_wx_core::Issue> =
         warnings_legacy_raw.iter().map(|item| issue_from_legacy(item)).collect();
-    let mut errors = Vec::new();
+    let validation = validate_taf(&parsed);
+    warnings.extend(validation.warnings);
+    let mut errors = validation.errors;
     let (warnings_legacy, errors_legacy) = finalize_issues(&mut warnings, &mut errors);
 
     DecodeResponse {
@@ -316,4 +645,104 @@ pub fn decode_taf(raw: &str, detail: DetailLevel, lang: &str) -> DecodeResponse
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faa_taf_statute_miles_and_clr() {
+        let raw = "TAF KJFK 011130Z 0112/0218 31015KT P6SM CLR BECMG 0116/0118 20012KT 1 1/2SM BR OVC005";
+        let (parsed, warnings) = parse_taf(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.dialect, Dialect::Faa);
+        assert_eq!(parsed.visibility.as_ref().map(|v| v.raw.as_str()), Some("P6SM"));
+        assert_eq!(parsed.clouds[0].amount, "CLR");
+        let trend = &parsed.trends[0];
+        assert_eq!(trend.visibility.as_ref().map(|v| v.raw.as_str()), Some("1 1/2SM"));
+    }
+
+    #[test]
+    fn icao_taf_rejects_statute_miles_and_keeps_remarks() {
+        let raw = "TAF EGLL 011110Z 0112/0218 24010KT P6SM BKN012 RMK NXT FCST BY 18Z";
+        let (parsed, warnings) = parse_taf(raw);
+        assert_eq!(parsed.dialect, Dialect::Icao);
+        assert_eq!(warnings, vec!["Unrecognized token: P6SM".to_string()]);
+        assert_eq!(parsed.rmk_raw.as_deref(), Some("NXT FCST BY 18Z"));
+    }
+
+    #[test]
+    fn fm_and_prob_change_groups() {
+        let raw = "TAF KJFK 241130Z 2412/2518 31015KT P6SM SCT025 FM241800 20012KT 3SM -RA BKN010 \
+                   PROB30 2420/2424 1SM TSRA OVC008CB PROB40 TEMPO 2502/2506 2SM BR";
+        let (parsed, warnings) = parse_taf(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.trends.len(), 3);
+
+        let fm = &parsed.trends[0];
+        assert_eq!(fm.kind, TafTrendKind::Fm);
+        let from = fm.from_time.as_ref().expect("FM time");
+        assert_eq!((from.day, from.hour, from.minute), (24, 18, 0));
+        assert!(fm.period.is_none());
+        assert_eq!(fm.weather, vec!["-RA".to_string()]);
+
+        let prob = &parsed.trends[1];
+        assert_eq!(prob.kind, TafTrendKind::Prob);
+        assert_eq!(prob.probability, Some(30));
+        assert_eq!(prob.period.as_ref().map(|p| p.from.hour), Some(20));
+
+        let prob_tempo = &parsed.trends[2];
+        assert_eq!(prob_tempo.kind, TafTrendKind::ProbTempo);
+        assert_eq!(prob_tempo.probability, Some(40));
+        assert_eq!(prob_tempo.weather, vec!["BR".to_string()]);
+    }
+
+    #[test]
+    fn temperature_groups_are_typed_and_checked() {
+        let raw = "TAF ZBAA 311730Z 3118/0124 06005MPS 9999 FEW020 TX02/0112Z TNM05/3121Z TX08/0212Z";
+        let (parsed, warnings) = parse_taf(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.temperatures.len(), 3);
+        let min = &parsed.temperatures[1];
+        assert_eq!(min.kind, TafTemperatureKind::Min);
+        assert_eq!(min.value_c, -5);
+        assert_eq!((min.at.day, min.at.hour), (31, 21));
+
+        let issues = temperature_issues(&parsed);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "temperature_outside_validity");
+        assert_eq!(issues[0].token.as_deref(), Some("TX08/0212Z"));
+    }
+
+    #[test]
+    fn military_hazard_groups() {
+        let raw = "TAF KBLV 011100Z 0112/0218 27012KT 9999 BKN030 620304 520606 WS020/27050KT QNH2992INS \
+                   BECMG 0118/0120 6000 -SN OVC015 640152 QNH2975INS";
+        let (parsed, warnings) = parse_taf(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.visibility.as_ref().map(|v| v.distance), Some(9999));
+        assert_eq!(parsed.icing[0].code, 2);
+        assert_eq!((parsed.icing[0].base_ft, parsed.icing[0].thickness_ft), (3000, 4000));
+        assert_eq!((parsed.turbulence[0].code, parsed.turbulence[0].base_ft), (2, 6000));
+        let shear = parsed.wind_shear.as_ref().expect("wind shear");
+        assert_eq!((shear.height_ft, shear.wind.speed), (2000, 50));
+        assert_eq!(parsed.qnh.as_ref().map(|p| p.value), Some(2992));
+        assert_eq!(parsed.trends[0].icing[0].code, 4);
+
+        let normalized = normalize_taf(&parsed);
+        assert!((normalized.qnh_hpa.unwrap() - 1013.2).abs() < 0.1);
+        assert_eq!(normalized.trends[0].qnh_inhg, Some(29.75));
+        let text = translate_taf(&normalized, DetailLevel::Normal, "zh-CN");
+        assert!(text.contains("积冰 轻度（云中） 3000-7000ft"), "{}", text);
+        assert!(text.contains("低空风切变 2000ft 处风 270° 50kt"), "{}", text);
+    }
 
+    #[test]
+    fn australian_inter_group() {
+        let raw = "TAF YSSY 240500Z 2406/2512 18012KT 9999 SCT030 INTER 2406/2410 4000 SHRA BKN010";
+        let (parsed, warnings) = parse_taf(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.dialect, Dialect::Australia);
+        assert_eq!(parsed.trends[0].kind, TafTrendKind::Inter);
+        assert_eq!(parsed.clouds.len(), 1);
+    }
+}
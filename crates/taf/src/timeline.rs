@@ -0,0 +1,242 @@
+//! Expands a normalized TAF into consecutive periods of prevailing conditions.
+//!
+//! Change groups are applied in message order:
+//!
+//! - `FM` replaces the whole forecast from its start time; elements it omits are cleared.
+//! - `NSW` (or `CAVOK`) in a change group clears the weather; `CAVOK`, `NSC` and `SKC`
+//!   clear the cloud layers. Otherwise an element absent from a change group persists.
+//! - `BECMG` changes only the elements it reports. During the transition window the
+//!   previous conditions remain prevailing and the new ones are listed as a `BECMG`
+//!   alternative; from the end of the window they become prevailing.
+//! - `TEMPO`, `PROB`, `PROB TEMPO` and `INTER` never change the prevailing conditions.
+//!   While their period overlaps a slot they are listed as alternatives, each being the
+//!   prevailing conditions with the group's elements applied on top.
+//!
+//! Slots are half-open: a slot `[start, end)` uses the prevailing conditions at `start`
+//! and lists every alternative whose period overlaps it.
+
+use aviation_wx_core::{CloudLayer, TafNormalized, TafTrendKind, TafTrendNormalized, UtcTime, ValidityPeriod, WindNormalized};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TafConditions {
+    pub wind: Option<WindNormalized>,
+    pub visibility_m: Option<u32>,
+    pub weather: Vec<String>,
+    pub clouds: Vec<CloudLayer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TafAlternative {
+    pub kind: TafTrendKind,
+    pub probability: Option<u8>,
+    pub period: ValidityPeriod,
+    pub conditions: TafConditions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TafTimelinePeriod {
+    pub start: UtcTime,
+    pub end: UtcTime,
+    pub prevailing: TafConditions,
+    pub alternatives: Vec<TafAlternative>,
+}
+
+/// Expands `taf` into slots of `step_minutes` covering its validity period.
+/// Returns an empty list when the TAF has no validity or `step_minutes` is zero.
+pub fn expand_timeline(taf: &TafNormalized, step_minutes: u32) -> Vec<TafTimelinePeriod> {
+    let Some(validity) = &taf.validity else {
+        return Vec::new();
+    };
+    if step_minutes == 0 {
+        return Vec::new();
+    }
+    let step = step_minutes as i64;
+    let total = validity.duration_minutes();
+    let mut periods = Vec::new();
+    let mut start = 0;
+    while start < total {
+        let end = (start + step).min(total);
+        periods.push(slot(taf, validity, start, end));
+        start = end;
+    }
+    periods
+}
+
+/// Hourly timeline, the granularity most consumers want.
+pub fn hourly_timeline(taf: &TafNormalized) -> Vec<TafTimelinePeriod> {
+    expand_timeline(taf, 60)
+}
+
+/// Conditions forecast at `time`, or `None` when it is outside the validity period.
+pub fn conditions_at(taf: &TafNormalized, time: &UtcTime) -> Option<TafTimelinePeriod> {
+    let validity = taf.validity.as_ref()?;
+    let offset = validity.offset_minutes(time);
+    if offset < 0 || offset >= validity.duration_minutes() {
+        return None;
+    }
+    Some(slot(taf, validity, offset, offset + 1))
+}
+
+fn slot(taf: &TafNormalized, validity: &ValidityPeriod, start: i64, end: i64) -> TafTimelinePeriod {
+    let mut prevailing = base_conditions(taf);
+    for trend in &taf.trends {
+        match trend.kind {
+            TafTrendKind::Fm => {
+                if let Some(from) = &trend.from_time {
+                    if validity.offset_minutes(from) <= start {
+                        prevailing = TafConditions::default();
+                        apply_trend(&mut prevailing, trend);
+                    }
+                }
+            }
+            TafTrendKind::Becmg => {
+                if let Some((_, to)) = trend_window(validity, trend) {
+                    if to <= start {
+                        apply_trend(&mut prevailing, trend);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut alternatives = Vec::new();
+    for trend in &taf.trends {
+        if trend.kind == TafTrendKind::Fm {
+            continue;
+        }
+        let Some((from, to)) = trend_window(validity, trend) else {
+            continue;
+        };
+        if from >= end || start >= to {
+            continue;
+        }
+        let mut conditions = prevailing.clone();
+        apply_trend(&mut conditions, trend);
+        alternatives.push(TafAlternative {
+            kind: trend.kind,
+            probability: trend.probability,
+            period: trend.period.clone().unwrap_or_else(|| validity.clone()),
+            conditions,
+        });
+    }
+
+    TafTimelinePeriod {
+        start: validity.time_at_offset(start),
+        end: validity.time_at_offset(end),
+        prevailing,
+        alternatives,
+    }
+}
+
+fn base_conditions(taf: &TafNormalized) -> TafConditions {
+    TafConditions {
+        wind: taf.wind.clone(),
+        visibility_m: taf.visibility_m,
+        weather: taf.weather.clone(),
+        clouds: taf.clouds.clone(),
+    }
+}
+
+fn trend_window(validity: &ValidityPeriod, trend: &TafTrendNormalized) -> Option<(i64, i64)> {
+    let period = trend.period.as_ref()?;
+    Some((validity.offset_minutes(&period.from), validity.offset_minutes(&period.to)))
+}
+
+fn apply_trend(conditions: &mut TafConditions, trend: &TafTrendNormalized) {
+    if let Some(wind) = &trend.wind {
+        conditions.wind = Some(wind.clone());
+    }
+    if let Some(vis) = trend.visibility_m {
+        conditions.visibility_m = Some(vis);
+    }
+    if trend.clears_weather || !trend.weather.is_empty() {
+        conditions.weather = trend.weather.clone();
+    }
+    if trend.clears_clouds || !trend.clouds.is_empty() {
+        conditions.clouds = trend.clouds.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_taf, parse_taf};
+
+    fn timeline(raw: &str) -> Vec<TafTimelinePeriod> {
+        let (parsed, _) = parse_taf(raw);
+        hourly_timeline(&normalize_taf(&parsed))
+    }
+
+    #[test]
+    fn becmg_applies_after_transition_window() {
+        let periods = timeline("TAF KJFK 011130Z 0112/0218 31015KT 9999 SCT025 BECMG 0116/0118 20012KT");
+        assert_eq!(periods.len(), 30);
+        let before = &periods[3];
+        assert_eq!(before.prevailing.wind.as_ref().and_then(|w| w.direction_deg), Some(310));
+        assert!(before.alternatives.is_empty());
+
+        let during = &periods[4];
+        assert_eq!((during.start.day, during.start.hour), (1, 16));
+        assert_eq!(during.prevailing.wind.as_ref().and_then(|w| w.direction_deg), Some(310));
+        assert_eq!(during.alternatives[0].kind, TafTrendKind::Becmg);
+        assert_eq!(during.alternatives[0].conditions.wind.as_ref().and_then(|w| w.direction_deg), Some(200));
+
+        let after = &periods[6];
+        assert_eq!(after.prevailing.wind.as_ref().and_then(|w| w.direction_deg), Some(200));
+        assert_eq!(after.prevailing.clouds.len(), 1);
+        assert!(after.alternatives.is_empty());
+    }
+
+    #[test]
+    fn fm_replaces_and_tempo_overlays() {
+        let raw = "TAF EGLL 011110Z 0112/0218 24010KT 8000 -DZ BKN012 TEMPO 0112/0115 3000 DZ BKN006 \
+                   FM011800 30015KT 9999 SCT030";
+        let periods = timeline(raw);
+        let tempo = &periods[1];
+        assert_eq!(tempo.prevailing.visibility_m, Some(8000));
+        assert_eq!(tempo.alternatives.len(), 1);
+        assert_eq!(tempo.alternatives[0].conditions.visibility_m, Some(3000));
+        assert_eq!(tempo.alternatives[0].conditions.wind.as_ref().map(|w| w.speed_kt), Some(10));
+
+        let fm = &periods[6];
+        assert_eq!((fm.start.day, fm.start.hour), (1, 18));
+        assert_eq!(fm.prevailing.visibility_m, Some(10000));
+        assert!(fm.prevailing.weather.is_empty());
+        assert_eq!(fm.prevailing.clouds[0].amount, "SCT");
+    }
+
+    #[test]
+    fn nsw_and_cavok_clear_weather_and_cloud() {
+        let raw = "TAF ZSPD 011100Z 0112/0218 09008KT 3000 RA BR VV003 BECMG 0114/0116 6000 NSW BKN010 \
+                   BECMG 0120/0122 CAVOK";
+        let (parsed, warnings) = parse_taf(raw);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(parsed.clouds[0].amount, "VV");
+        assert_eq!(parsed.clouds[0].height_ft, Some(300));
+        assert!(parsed.trends[0].clears_weather);
+        assert!(parsed.trends[0].weather.is_empty());
+
+        let periods = hourly_timeline(&normalize_taf(&parsed));
+        let after_nsw = &periods[5];
+        assert!(after_nsw.prevailing.weather.is_empty());
+        assert_eq!(after_nsw.prevailing.clouds[0].amount, "BKN");
+
+        let after_cavok = &periods[11];
+        assert_eq!(after_cavok.prevailing.visibility_m, Some(10000));
+        assert!(after_cavok.prevailing.clouds.is_empty());
+        assert_eq!(after_cavok.prevailing.wind.as_ref().map(|w| w.speed_kt), Some(8));
+    }
+
+    #[test]
+    fn conditions_at_handles_month_rollover() {
+        let (parsed, _) = parse_taf("TAF ZBAA 301730Z 3018/0124 06005MPS 9999 FEW020 FM010600 18004MPS 6000 BR");
+        let normalized = normalize_taf(&parsed);
+        let periods = hourly_timeline(&normalized);
+        assert_eq!(periods.len(), 30);
+        let at = conditions_at(&normalized, &UtcTime { day: 1, hour: 7, minute: 30 }).expect("in validity");
+        assert_eq!(at.prevailing.weather, vec!["BR".to_string()]);
+        assert!(conditions_at(&normalized, &UtcTime { day: 2, hour: 1, minute: 0 }).is_none());
+    }
+}
@@ -0,0 +1,236 @@
+//! Structural checks of a parsed TAF against the ICAO Annex 3 layout rules.
+
+use aviation_wx_core::{issue, Issue, TafNormalized, TafParsed, TafTrend, TafTrendKind, TafTrendNormalized, ValidityPeriod};
+use serde::{Deserialize, Serialize};
+
+use crate::timeline::conditions_at;
+use crate::{describe_trend_kind, normalize_taf, temperature_issues};
+
+const MAX_VALIDITY_MINUTES: i64 = 30 * 60;
+const MAX_BECMG_MINUTES: i64 = 4 * 60;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TafValidation {
+    pub warnings: Vec<Issue>,
+    pub errors: Vec<Issue>,
+}
+
+impl TafValidation {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Validates the structure of `parsed`. Errors make the TAF unusable as issued
+/// (missing validity, change groups outside it, FM groups out of order); warnings
+/// flag groups that break Annex 3 conventions but can still be interpreted.
+pub fn validate_taf(parsed: &TafParsed) -> TafValidation {
+    let mut report = TafValidation::default();
+    let Some(validity) = &parsed.validity else {
+        report
+            .errors
+            .push(issue("validity_missing", "TAF has no validity period.", None));
+        return report;
+    };
+
+    let duration = validity.duration_minutes();
+    if duration <= 0 || duration > MAX_VALIDITY_MINUTES {
+        report.warnings.push(issue(
+            "validity_invalid",
+            format!("TAF validity of {} minutes is outside 1 to 30 hours.", duration),
+            Some(format_period(validity)),
+        ));
+    }
+
+    let normalized = normalize_taf(parsed);
+    let mut last_fm: Option<i64> = None;
+    let mut tempo_windows: Vec<(TafTrendKind, i64, i64, String)> = Vec::new();
+
+    for (idx, trend) in parsed.trends.iter().enumerate() {
+        let label = trend_label(trend);
+
+        if let Some(probability) = trend.probability {
+            if probability != 30 && probability != 40 {
+                report.warnings.push(issue(
+                    "probability_invalid",
+                    format!("{} uses probability {}%; only 30 and 40 are allowed.", label, probability),
+                    Some(label.clone()),
+                ));
+            }
+        }
+
+        if trend.kind == TafTrendKind::Fm {
+            let Some(from) = &trend.from_time else {
+                continue;
+            };
+            let offset = validity.offset_minutes(from);
+            if offset < 0 || offset >= duration {
+                report.errors.push(issue(
+                    "trend_outside_validity",
+                    format!("{} starts outside the TAF validity period.", label),
+                    Some(label.clone()),
+                ));
+            }
+            if let Some(previous) = last_fm {
+                if offset <= previous {
+                    report.errors.push(issue(
+                        "fm_not_monotonic",
+                        format!("{} does not start after the previous FM group.", label),
+                        Some(label.clone()),
+                    ));
+                }
+            }
+            last_fm = Some(offset);
+        } else {
+            let Some(period) = &trend.period else {
+                report.warnings.push(issue(
+                    "trend_period_missing",
+                    format!("{} has no time period.", label),
+                    Some(label.clone()),
+                ));
+                continue;
+            };
+            if !validity.covers(period) {
+                report.errors.push(issue(
+                    "trend_outside_validity",
+                    format!("{} is not within the TAF validity period.", label),
+                    Some(label.clone()),
+                ));
+            }
+            let from = validity.offset_minutes(&period.from);
+            let to = validity.offset_minutes(&period.to);
+            if to <= from {
+                report.warnings.push(issue(
+                    "trend_period_invalid",
+                    format!("{} ends before it starts.", label),
+                    Some(label.clone()),
+                ));
+            }
+            if trend.kind == TafTrendKind::Becmg && to - from > MAX_BECMG_MINUTES {
+                report.warnings.push(issue(
+                    "becmg_too_long",
+                    format!("{} spans more than 4 hours.", label),
+                    Some(label.clone()),
+                ));
+            }
+            // Temporary fluctuations of the same kind (TEMPO, PROB TEMPO or INTER) must not
+            // overlap; a PROB30 TEMPO inside a TEMPO window is a usual refinement.
+            if matches!(trend.kind, TafTrendKind::Tempo | TafTrendKind::ProbTempo | TafTrendKind::Inter) {
+                for (other_kind, other_from, other_to, other_label) in &tempo_windows {
+                    if *other_kind == trend.kind && from < *other_to && *other_from < to {
+                        report.warnings.push(issue(
+                            "tempo_overlap",
+                            format!("{} overlaps {}.", label, other_label),
+                            Some(label.clone()),
+                        ));
+                    }
+                }
+                tempo_windows.push((trend.kind, from, to, label.clone()));
+            }
+        }
+
+        if !changes_something(&normalized, trend, &normalized.trends[idx]) {
+            report.warnings.push(issue(
+                "trend_no_change",
+                format!("{} does not change any forecast element.", label),
+                Some(label.clone()),
+            ));
+        }
+    }
+
+    report.warnings.extend(temperature_issues(parsed));
+    report
+}
+
+fn changes_something(normalized: &TafNormalized, trend: &TafTrend, trend_normalized: &TafTrendNormalized) -> bool {
+    if trend_normalized.wind.is_none()
+        && trend_normalized.visibility_m.is_none()
+        && trend_normalized.weather.is_empty()
+        && trend_normalized.clouds.is_empty()
+        && !trend_normalized.clears_weather
+        && !trend_normalized.clears_clouds
+    {
+        return !trend_normalized.icing.is_empty()
+            || !trend_normalized.turbulence.is_empty()
+            || trend_normalized.wind_shear.is_some()
+            || trend_normalized.qnh_inhg.is_some();
+    }
+    if trend.kind == TafTrendKind::Fm {
+        return true;
+    }
+    let Some(period) = &trend.period else {
+        return true;
+    };
+    let Some(before) = conditions_at(normalized, &period.from) else {
+        return true;
+    };
+    before
+        .alternatives
+        .iter()
+        .filter(|alt| alt.kind == trend.kind && alt.period == *period)
+        .any(|alt| alt.conditions != before.prevailing)
+}
+
+fn trend_label(trend: &TafTrend) -> String {
+    let kind = describe_trend_kind(trend.kind, trend.probability);
+    if let Some(from) = &trend.from_time {
+        return format!("FM{:02}{:02}{:02}", from.day, from.hour, from.minute);
+    }
+    match &trend.period {
+        Some(period) => format!("{} {}", kind, format_period(period)),
+        None => kind,
+    }
+}
+
+fn format_period(period: &ValidityPeriod) -> String {
+    format!(
+        "{:02}{:02}/{:02}{:02}",
+        period.from.day, period.from.hour, period.to.day, period.to.hour
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_taf;
+
+    fn codes(issues: &[Issue]) -> Vec<&str> {
+        issues.iter().map(|item| item.code.as_str()).collect()
+    }
+
+    #[test]
+    fn well_formed_taf_passes() {
+        let (parsed, _) = parse_taf("TAF KJFK 011130Z 0112/0218 31015KT 9999 SCT025 BECMG 0116/0118 20012KT");
+        let report = validate_taf(&parsed);
+        assert!(report.is_valid());
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    }
+
+    #[test]
+    fn reports_annex3_violations() {
+        let raw = "TAF EGLL 011110Z 0112/0218 24010KT 8000 BKN012 BECMG 0112/0118 18010KT \
+                   TEMPO 0113/0116 3000 TEMPO 0115/0117 2000 TEMPO 0210/0220 4000 \
+                   FM020300 20010KT 9999 FM020100 22010KT BECMG 0204/0205 22010KT";
+        let (parsed, _) = parse_taf(raw);
+        let report = validate_taf(&parsed);
+        assert_eq!(codes(&report.errors), vec!["trend_outside_validity", "fm_not_monotonic"]);
+        assert_eq!(codes(&report.warnings), vec!["becmg_too_long", "tempo_overlap", "trend_no_change"]);
+        assert_eq!(report.warnings[2].token.as_deref(), Some("BECMG 0204/0205"));
+    }
+
+    #[test]
+    fn reports_overlapping_prob_tempo_and_inter_groups() {
+        let raw = "TAF YSSY 011100Z 0112/0218 18012KT 9999 SCT030 TEMPO 0114/0118 4000 SHRA \
+                   PROB30 TEMPO 0114/0116 1500 TSRA PROB30 TEMPO 0115/0117 2000 TSRA \
+                   INTER 0202/0206 3000 SHRA INTER 0205/0208 3000 DZ";
+        let (parsed, _) = parse_taf(raw);
+        let report = validate_taf(&parsed);
+        let overlaps: Vec<_> = report
+            .warnings
+            .iter()
+            .filter(|item| item.code == "tempo_overlap")
+            .map(|item| item.token.as_deref().unwrap_or_default())
+            .collect();
+        assert_eq!(overlaps, vec!["PROB30 TEMPO 0115/0117", "INTER 0205/0208"]);
+    }
+}
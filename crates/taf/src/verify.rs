@@ -0,0 +1,265 @@
+//! Verification of a TAF against the METARs observed during its validity period.
+//!
+//! Each observation is compared with the forecast for its issue time (see
+//! [`conditions_at`]). Category elements count as a hit when the observed category
+//! matches the prevailing conditions or any alternative (`BECMG` transition, `TEMPO`,
+//! `PROB`, `INTER`) in force at that time. Wind errors are measured against the
+//! prevailing wind only.
+
+use aviation_wx_core::{ceiling_ft, flight_category, FlightCategory, MetarNormalized, TafNormalized, UtcTime};
+use serde::{Deserialize, Serialize};
+
+use crate::timeline::{conditions_at, TafConditions};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryScore<T> {
+    pub forecast: T,
+    pub observed: T,
+    pub hit: bool,
+}
+
+/// Contingency outcome for the occurrence of significant weather.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OccurrenceScore {
+    Hit,
+    Miss,
+    FalseAlarm,
+    CorrectNegative,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TafVerificationEntry {
+    pub time: UtcTime,
+    pub wind_direction_error_deg: Option<u16>,
+    pub wind_speed_error_kt: Option<i32>,
+    pub visibility: Option<CategoryScore<String>>,
+    pub ceiling: CategoryScore<String>,
+    pub weather: OccurrenceScore,
+    pub flight_category: Option<CategoryScore<FlightCategory>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TafVerificationSummary {
+    pub observations: usize,
+    pub skipped: usize,
+    pub mean_wind_direction_error_deg: Option<f32>,
+    pub mean_wind_speed_error_kt: Option<f32>,
+    pub visibility_hit_rate: Option<f32>,
+    pub ceiling_hit_rate: Option<f32>,
+    pub flight_category_hit_rate: Option<f32>,
+    pub weather_hits: usize,
+    pub weather_misses: usize,
+    pub weather_false_alarms: usize,
+    pub weather_correct_negatives: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TafVerification {
+    pub station: Option<String>,
+    pub entries: Vec<TafVerificationEntry>,
+    pub summary: TafVerificationSummary,
+}
+
+/// Scores `taf` against `observations`. METARs for another station, without an issue
+/// time or outside the validity period are counted in `summary.skipped`.
+pub fn verify_taf(taf: &TafNormalized, observations: &[MetarNormalized]) -> TafVerification {
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for metar in observations {
+        match verify_observation(taf, metar) {
+            Some(entry) => entries.push(entry),
+            None => skipped += 1,
+        }
+    }
+    let summary = summarize(&entries, skipped);
+    TafVerification {
+        station: taf.station.clone(),
+        entries,
+        summary,
+    }
+}
+
+fn verify_observation(taf: &TafNormalized, metar: &MetarNormalized) -> Option<TafVerificationEntry> {
+    if let (Some(expected), Some(observed)) = (&taf.station, &metar.station) {
+        if expected != observed {
+            return None;
+        }
+    }
+    let time = metar.issue_time.clone()?;
+    let forecast = conditions_at(taf, &time)?;
+    let candidates: Vec<&TafConditions> = std::iter::once(&forecast.prevailing)
+        .chain(forecast.alternatives.iter().map(|alt| &alt.conditions))
+        .collect();
+
+    let (wind_direction_error_deg, wind_speed_error_kt) = match (&forecast.prevailing.wind, &metar.wind) {
+        (Some(expected), Some(observed)) => {
+            let direction = match (expected.direction_deg, observed.direction_deg) {
+                (Some(a), Some(b)) if !expected.variable && !observed.variable => Some(direction_error(a, b)),
+                _ => None,
+            };
+            (direction, Some(observed.speed_kt as i32 - expected.speed_kt as i32))
+        }
+        _ => (None, None),
+    };
+
+    let visibility = metar.visibility_m.and_then(|observed| {
+        let observed_band = visibility_band(observed);
+        let forecast_band = visibility_band(forecast.prevailing.visibility_m?);
+        let hit = candidates
+            .iter()
+            .any(|c| c.visibility_m.map(visibility_band) == Some(observed_band));
+        Some(CategoryScore {
+            forecast: forecast_band.to_string(),
+            observed: observed_band.to_string(),
+            hit,
+        })
+    });
+
+    let observed_ceiling = ceiling_band(ceiling_ft(&metar.clouds));
+    let ceiling = CategoryScore {
+        forecast: ceiling_band(ceiling_ft(&forecast.prevailing.clouds)).to_string(),
+        observed: observed_ceiling.to_string(),
+        hit: candidates
+            .iter()
+            .any(|c| ceiling_band(ceiling_ft(&c.clouds)) == observed_ceiling),
+    };
+
+    let observed_weather = !metar.weather.is_empty();
+    let weather = match (observed_weather, candidates.iter().any(|c| !c.weather.is_empty())) {
+        (true, true) => OccurrenceScore::Hit,
+        (true, false) => OccurrenceScore::Miss,
+        (false, _) if !forecast.prevailing.weather.is_empty() => OccurrenceScore::FalseAlarm,
+        (false, _) => OccurrenceScore::CorrectNegative,
+    };
+
+    let flight = flight_category(metar.visibility_m, ceiling_ft(&metar.clouds)).and_then(|observed| {
+        let category = |c: &TafConditions| flight_category(c.visibility_m, ceiling_ft(&c.clouds));
+        Some(CategoryScore {
+            forecast: category(&forecast.prevailing)?,
+            observed,
+            hit: candidates.iter().any(|c| category(c) == Some(observed)),
+        })
+    });
+
+    Some(TafVerificationEntry {
+        time,
+        wind_direction_error_deg,
+        wind_speed_error_kt,
+        visibility,
+        ceiling,
+        weather,
+        flight_category: flight,
+    })
+}
+
+fn summarize(entries: &[TafVerificationEntry], skipped: usize) -> TafVerificationSummary {
+    let count = |score: OccurrenceScore| entries.iter().filter(|e| e.weather == score).count();
+    TafVerificationSummary {
+        observations: entries.len(),
+        skipped,
+        mean_wind_direction_error_deg: mean(entries.iter().filter_map(|e| e.wind_direction_error_deg.map(f32::from))),
+        mean_wind_speed_error_kt: mean(entries.iter().filter_map(|e| e.wind_speed_error_kt.map(|v| v.abs() as f32))),
+        visibility_hit_rate: hit_rate(entries.iter().filter_map(|e| e.visibility.as_ref().map(|s| s.hit))),
+        ceiling_hit_rate: hit_rate(entries.iter().map(|e| e.ceiling.hit)),
+        flight_category_hit_rate: hit_rate(entries.iter().filter_map(|e| e.flight_category.as_ref().map(|s| s.hit))),
+        weather_hits: count(OccurrenceScore::Hit),
+        weather_misses: count(OccurrenceScore::Miss),
+        weather_false_alarms: count(OccurrenceScore::FalseAlarm),
+        weather_correct_negatives: count(OccurrenceScore::CorrectNegative),
+    }
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, n) = values.fold((0.0, 0), |(sum, n), v| (sum + v, n + 1));
+    (n > 0).then(|| sum / n as f32)
+}
+
+fn hit_rate(hits: impl Iterator<Item = bool>) -> Option<f32> {
+    mean(hits.map(|hit| if hit { 1.0 } else { 0.0 }))
+}
+
+fn direction_error(a: u16, b: u16) -> u16 {
+    let diff = (a as i32 - b as i32).rem_euclid(360) as u16;
+    diff.min(360 - diff)
+}
+
+fn visibility_band(visibility_m: u32) -> &'static str {
+    match visibility_m {
+        0..=799 => "below_800m",
+        800..=1499 => "800m_to_1500m",
+        1500..=2999 => "1500m_to_3000m",
+        3000..=4999 => "3000m_to_5000m",
+        _ => "5000m_or_more",
+    }
+}
+
+fn ceiling_band(ceiling: Option<u16>) -> &'static str {
+    match ceiling {
+        Some(0..=499) => "below_500ft",
+        Some(500..=999) => "500ft_to_1000ft",
+        Some(1000..=2999) => "1000ft_to_3000ft",
+        _ => "3000ft_or_more",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalize_taf, parse_taf};
+    use aviation_wx_metar::{normalize_metar, parse_metar};
+
+    fn taf(raw: &str) -> TafNormalized {
+        normalize_taf(&parse_taf(raw).0)
+    }
+
+    fn metar(raw: &str) -> MetarNormalized {
+        normalize_metar(&parse_metar(raw).0)
+    }
+
+    #[test]
+    fn scores_each_element() {
+        let forecast = taf("TAF EGLL 011110Z 0112/0218 24010KT 8000 BKN012 TEMPO 0112/0116 3000 RA BKN006");
+        let report = verify_taf(
+            &forecast,
+            &[
+                metar("METAR EGLL 011250Z 26014KT 3500 RA BKN007 12/10 Q1010"),
+                metar("METAR EGLL 011750Z 23008KT 9999 SCT040 14/09 Q1012"),
+            ],
+        );
+        assert_eq!(report.entries.len(), 2);
+        let tempo = &report.entries[0];
+        assert_eq!(tempo.wind_direction_error_deg, Some(20));
+        assert_eq!(tempo.wind_speed_error_kt, Some(4));
+        assert!(tempo.visibility.as_ref().unwrap().hit);
+        assert!(tempo.ceiling.hit);
+        assert_eq!(tempo.weather, OccurrenceScore::Hit);
+        assert_eq!(tempo.flight_category.as_ref().unwrap().observed, FlightCategory::Ifr);
+        assert!(tempo.flight_category.as_ref().unwrap().hit);
+
+        let later = &report.entries[1];
+        assert!(later.visibility.as_ref().unwrap().hit);
+        assert_eq!(later.ceiling.forecast, "1000ft_to_3000ft");
+        assert_eq!(later.ceiling.observed, "3000ft_or_more");
+        assert_eq!(later.weather, OccurrenceScore::CorrectNegative);
+        assert_eq!(report.summary.ceiling_hit_rate, Some(0.5));
+        assert_eq!(report.summary.mean_wind_speed_error_kt, Some(3.0));
+    }
+
+    #[test]
+    fn skips_other_stations_and_times_outside_validity() {
+        let forecast = taf("TAF KJFK 011130Z 0112/0218 31015KT P6SM -SHRA SCT025");
+        let report = verify_taf(
+            &forecast,
+            &[
+                metar("METAR KJFK 011251Z 35015KT 10SM FEW030 20/10 A3001"),
+                metar("METAR KLGA 011251Z 31015KT 10SM FEW030 20/10 A3001"),
+                metar("METAR KJFK 021851Z 31015KT 10SM FEW030 20/10 A3001"),
+            ],
+        );
+        assert_eq!(report.summary.observations, 1);
+        assert_eq!(report.summary.skipped, 2);
+        assert_eq!(report.entries[0].wind_direction_error_deg, Some(40));
+        assert_eq!(report.summary.weather_false_alarms, 1);
+    }
+}
@@ -39,13 +39,13 @@ function parseTable(part) {
 const subject = parseTable(subjectPart);
 const condition = parseTable(conditionPart);
 
-fs.mkdirSync("web/src/data/lexicons", { recursive: true });
+fs.mkdirSync("crates/notam/lexicons", { recursive: true });
 fs.writeFileSync(
-  "web/src/data/lexicons/notam_q_subject_en.json",
+  "crates/notam/lexicons/notam_q_subject_en.json",
   JSON.stringify(subject, null, 2)
 );
 fs.writeFileSync(
-  "web/src/data/lexicons/notam_q_condition_en.json",
+  "crates/notam/lexicons/notam_q_condition_en.json",
   JSON.stringify(condition, null, 2)
 );
 
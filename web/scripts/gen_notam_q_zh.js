@@ -1,10 +1,10 @@
 import fs from "node:fs";
 
 const subject = JSON.parse(
-  fs.readFileSync("web/src/data/lexicons/notam_q_subject_en.json", "utf8")
+  fs.readFileSync("crates/notam/lexicons/notam_q_subject_en.json", "utf8")
 );
 const condition = JSON.parse(
-  fs.readFileSync("web/src/data/lexicons/notam_q_condition_en.json", "utf8")
+  fs.readFileSync("crates/notam/lexicons/notam_q_condition_en.json", "utf8")
 );
 
 const dictionary = [
@@ -157,11 +157,11 @@ const subjectZh = translateMap(subject);
 const conditionZh = translateMap(condition);
 
 fs.writeFileSync(
-  "web/src/data/lexicons/notam_q_subject_zh.json",
+  "crates/notam/lexicons/notam_q_subject_zh.json",
   JSON.stringify(subjectZh, null, 2)
 );
 fs.writeFileSync(
-  "web/src/data/lexicons/notam_q_condition_zh.json",
+  "crates/notam/lexicons/notam_q_condition_zh.json",
   JSON.stringify(conditionZh, null, 2)
 );
 
@@ -12,6 +12,7 @@ const altimeterRe = /^A\d{4}$/;
 const cloudRe = /^(FEW|SCT|BKN|OVC)\d{3}.*$/;
 const cloudAmountRe = /^(FEW|SCT|BKN|OVC|VV)(\d{3})/;
 const validityRe = /^\d{4}\/\d{4}$/;
+const changeIndicatorRe = /^(BECMG|TEMPO|INTER|FM\d{6}|PROB\d{2})$/;
 
 function classifyToken({ token, parsed, normalized, messageType, t }) {
   const upper = token.toUpperCase();
@@ -74,7 +75,7 @@ function classifyToken({ token, parsed, normalized, messageType, t }) {
     return { label: t("analysis.labels.trend"), fieldKey: "trend" };
   }
 
-  if (parsed?.trends?.some((trend) => trend.kind === upper)) {
+  if (parsed?.trends?.length && changeIndicatorRe.test(upper)) {
     return { label: t("analysis.labels.trend"), fieldKey: "trend" };
   }
 
@@ -279,7 +280,9 @@ export function buildTokenAnalysis({ data, t, locale, messageType }) {
       };
     }
     if (messageType === "taf") {
-      if (upper === "BECMG" || upper === "TEMPO") {
+      const previous = tokens[index - 1]?.toUpperCase() || "";
+      const afterProb = /^PROB\d{2}$/.test(previous) && (upper === "TEMPO" || upper === "INTER");
+      if (changeIndicatorRe.test(upper) && !afterProb) {
         inTrend = true;
         trendIndex += 1;
       }
@@ -4,6 +4,7 @@ import {
   formatRvr,
   formatStationDisplay,
   formatTemperature,
+  formatTrendKind,
   formatUtcTime,
   formatValidity,
   formatVisibility,
@@ -137,10 +138,11 @@ export function buildExplanation({ data, t, locale, stationInfo, messageType, de
       lines.push(t("explain.taf.clouds", { clouds: formatClouds(parsed.clouds) }));
     }
     if (parsed?.temperatures?.length) {
-      lines.push(t("explain.taf.temperature", { temp: parsed.temperatures.join(", ") }));
+      const temp = parsed.temperatures.map((item) => item.raw).join(", ");
+      lines.push(t("explain.taf.temperature", { temp }));
     }
     if (parsed?.trends?.length) {
-      const trends = parsed.trends.map((trend) => trend.kind).join(", ");
+      const trends = parsed.trends.map(formatTrendKind).join(", ");
       lines.push(t("explain.taf.trends", { trends }));
     }
   }
@@ -4,16 +4,17 @@ import {
   formatRvr,
   formatStationDisplay,
   formatTemperature,
+  formatTrendKind,
   formatUtcTime,
   formatValidity,
   formatVisibility,
   formatWind,
 } from "../../utils/format.js";
 import { getNormalized, getParsed, getStationCode } from "../../utils/message.js";
-import qSubject from "../../data/lexicons/notam_q_subject.json";
-import qCondition from "../../data/lexicons/notam_q_condition.json";
-import firCodes from "../../data/lexicons/fir_codes.json";
-import notamBodyLexicon from "../../data/lexicons/notam_body.json";
+import qSubject from "@lexicons/notam_q_subject.json";
+import qCondition from "@lexicons/notam_q_condition.json";
+import firCodes from "@lexicons/fir_codes.json";
+import notamBodyLexicon from "@lexicons/notam_body.json";
 
 const WEATHER_DESCRIPTORS = new Set([
   "MI",
@@ -616,29 +617,24 @@ export function buildFieldExplainList({ data, t, locale, stationInfo, messageTyp
       );
     }
     if (parsed?.temperatures?.length) {
-      const explainTemp = (token) => {
-        const match = token.match(/^T([XN])M?(\d{2})\/(\d{2})(\d{2})Z$/);
-        if (!match) {
-          return token;
-        }
-        const kind = match[1] === "X" ? t("taf.temp.max") : t("taf.temp.min");
-        const tempSign = token.includes("M") ? "-" : "";
-        const temp = `${tempSign}${match[2]}°C`;
-        const day = match[3];
-        const hour = match[4];
+      const explainTemp = (item) => {
+        const kind = item.kind === "max" ? t("taf.temp.max") : t("taf.temp.min");
+        const temp = `${item.value_c}°C`;
+        const day = String(item.at.day).padStart(2, "0");
+        const hour = String(item.at.hour).padStart(2, "0");
         return t("taf.temp.explain", { kind, temp, time: `${day} ${hour}:00 UTC` });
       };
       parsed.temperatures.forEach((temp, index) => {
         pushItem(
           `temp_${index}`,
           t("fields.temperature"),
-          temp,
+          temp.raw,
           explainTemp(temp)
         );
       });
     }
     if (parsed?.trends?.length) {
-      const summary = parsed.trends.map((trend) => trend.kind).join(", ");
+      const summary = parsed.trends.map(formatTrendKind).join(", ");
       pushItem("trends", t("fields.trend"), summary);
       parsed.trends.forEach((trend, index) => {
         const trendWind = normalized?.trends?.[index]?.wind || trend.wind;
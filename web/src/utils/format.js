@@ -16,6 +16,23 @@ export function formatValidity(period) {
   return `${formatUtcTime(period.from)} - ${formatUtcTime(period.to)}`;
 }
 
+export function formatTrendKind(trend) {
+  if (!trend?.kind) {
+    return "";
+  }
+  const probability = trend.probability != null ? `PROB${trend.probability}` : "";
+  switch (trend.kind) {
+    case "PROB":
+      return probability || "PROB";
+    case "PROB_TEMPO":
+      return `${probability || "PROB"} TEMPO`;
+    case "INTER":
+      return probability ? `${probability} INTER` : "INTER";
+    default:
+      return trend.kind;
+  }
+}
+
 export function formatWind(wind) {
   if (!wind) {
     return "";
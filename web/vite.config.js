@@ -1,11 +1,24 @@
+import { fileURLToPath } from "node:url";
 import { defineConfig } from "vite";
 import react from "@vitejs/plugin-react";
 
+// The NOTAM lexicons live in the Rust crate, which embeds them; the web client imports
+// the same files.
+const lexicons = fileURLToPath(new URL("../crates/notam/lexicons", import.meta.url));
+
 export default defineConfig({
   plugins: [react()],
   base: process.env.VITE_BASE || "/",
+  resolve: {
+    alias: {
+      "@lexicons": lexicons
+    }
+  },
   server: {
     port: 5173,
-    host: true
+    host: true,
+    fs: {
+      allow: [".", lexicons]
+    }
   }
 });